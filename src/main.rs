@@ -1,10 +1,15 @@
 use anyhow::{Context, Result};
-use chrono::{Duration, Local, NaiveDate};
+use chrono::{Datelike, DateTime, Duration, Local, NaiveDate, NaiveDateTime, Utc};
 use clap::{Parser, Subcommand};
+use exif::{In, Tag, Value};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::thread;
 use walkdir::WalkDir;
 
 // --- Constants & Config ---
@@ -63,11 +68,99 @@ const TZ_CITIES_DATA: &[(&str, i32, &str)] = &[
     ("Yangon", 9, "+06:30"),
 ];
 
+/// How `cmd_organize` buckets images into destination directories.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum BinStrategy {
+    Day,
+    Week,
+    Month,
+    Year,
+    /// Cluster by time gaps between consecutive photos instead of a fixed
+    /// calendar bucket; see `assign_event_dirs`.
+    Event,
+}
+
+impl BinStrategy {
+    /// exiftool `-d` strftime pattern for bins that are a pure function of a
+    /// single image's `DateTimeOriginal`. `Event` isn't — it depends on
+    /// neighbouring photos — so it has no pattern and is handled separately
+    /// in `cmd_organize` via `assign_event_dirs`.
+    fn strftime_pattern(self) -> Option<&'static str> {
+        match self {
+            BinStrategy::Day => Some("%Y-%m-%d"),
+            BinStrategy::Week => Some("%G-W%V"),
+            BinStrategy::Month => Some("%Y-%m"),
+            BinStrategy::Year => Some("%Y"),
+            BinStrategy::Event => None,
+        }
+    }
+}
+
+/// Output format for the `Catalog` command.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum CatalogFormat {
+    Csv,
+    Json,
+}
+
+/// Output format for a `--plan-format` dry-run rename/organize preview.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum PlanFormat {
+    Table,
+    Json,
+}
+
+/// One `source -> destination` mapping in a dry-run rename/organize plan.
+#[derive(Debug, Clone, Serialize)]
+struct PlanEntry {
+    source: String,
+    destination: String,
+    collision: bool,
+}
+
+/// Prints a computed rename/organize plan, flagging any destination that
+/// more than one source maps to so collisions are visible before anything
+/// is actually moved.
+fn print_plan(entries: &[PlanEntry], format: PlanFormat) -> Result<()> {
+    match format {
+        PlanFormat::Json => println!("{}", serde_json::to_string_pretty(entries)?),
+        PlanFormat::Table => {
+            let width = entries.iter().map(|e| e.source.len()).max().unwrap_or(0);
+            for entry in entries {
+                let marker = if entry.collision { "  [COLLISION]" } else { "" };
+                println!(
+                    "{:<width$} -> {}{}",
+                    entry.source,
+                    entry.destination,
+                    marker,
+                    width = width
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Marks every `PlanEntry` whose destination is shared with another entry.
+fn flag_collisions(entries: &mut [PlanEntry]) {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for entry in entries.iter() {
+        *counts.entry(entry.destination.clone()).or_insert(0) += 1;
+    }
+    for entry in entries.iter_mut() {
+        if counts[&entry.destination] > 1 {
+            entry.collision = true;
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 struct AppConfig {
     suffixes: Vec<String>,
     timerange: u64,
     dry_run: bool,
+    jobs: usize,
+    refresh_tz_cache: bool,
 }
 
 // --- CLI Definitions ---
@@ -87,6 +180,14 @@ struct Cli {
     )]
     suffix: Vec<String>,
 
+    /// Maximum number of directories/downloads to process concurrently
+    #[arg(short = 'j', long, default_value_t = 4)]
+    jobs: usize,
+
+    /// Bypass the per-directory timezone detection cache and recompute every offset
+    #[arg(long, default_value_t = false)]
+    refresh_tz_cache: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -97,6 +198,9 @@ enum Commands {
     Rename {
         #[arg(required = true)]
         paths: Vec<PathBuf>,
+        /// Compute and print the planned source -> destination mapping instead of renaming
+        #[arg(long, value_enum)]
+        plan_format: Option<PlanFormat>,
     },
     /// set time and timezone on pictures
     SetTime {
@@ -131,11 +235,65 @@ enum Commands {
         /// Files or directories to process
         paths: Vec<PathBuf>,
     },
+    /// Export a structured metadata catalog (CSV/JSON) for a set of photos
+    Catalog {
+        /// Files or directories to process
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// Output format
+        #[arg(long, value_enum, default_value = "csv")]
+        format: CatalogFormat,
+    },
     /// Organize photos into directories by date (YYYY-MM-DD)
     Organize {
         /// Directories to organize
         #[arg(required = true)]
         dirs: Vec<PathBuf>,
+        /// Delete content-duplicate images instead of moving them to a `duplicates/` folder
+        #[arg(long, default_value_t = false)]
+        delete_duplicates: bool,
+        /// How to bucket images into destination directories
+        #[arg(long, value_enum, default_value = "day")]
+        bin: BinStrategy,
+        /// With `--bin event`, start a new event when the gap since the previous photo exceeds this many hours
+        #[arg(long, default_value_t = 4)]
+        event_gap_hours: i64,
+        /// Bucket into fixed-length windows instead of a calendar period, e.g. "6h", "30m", "2d" (overrides --bin)
+        #[arg(long)]
+        bin_duration: Option<String>,
+        /// Compute and print the planned source -> destination mapping instead of organizing
+        #[arg(long, value_enum)]
+        plan_format: Option<PlanFormat>,
+    },
+    /// Thin an organized archive's `YYYY-MM-DD` folders using keep-N retention rules
+    Prune {
+        /// Directories containing `YYYY-MM-DD` date folders to prune
+        #[arg(required = true)]
+        dirs: Vec<PathBuf>,
+        /// Always keep this many of the most recent date folders
+        #[arg(long, default_value_t = 0)]
+        keep_last: usize,
+        /// Keep one date folder per day, for this many days
+        #[arg(long, default_value_t = 0)]
+        keep_daily: usize,
+        /// Keep one date folder per ISO week, for this many weeks
+        #[arg(long, default_value_t = 0)]
+        keep_weekly: usize,
+        /// Keep one date folder per calendar month, for this many months
+        #[arg(long, default_value_t = 0)]
+        keep_monthly: usize,
+        /// Keep one date folder per calendar year, for this many years
+        #[arg(long, default_value_t = 0)]
+        keep_yearly: usize,
+    },
+    /// Split a merged GPX track into one file per `--by`-named date folder
+    BinGpx {
+        /// Organized directories containing `--by`-named date folders
+        #[arg(required = true)]
+        dirs: Vec<PathBuf>,
+        /// How the existing date folders are named
+        #[arg(long, value_enum, default_value = "day")]
+        by: BinStrategy,
     },
     /// Process photos: Shift to UTC, Organize, Geotag, Set Time (with DST), Rename
     Process {
@@ -151,6 +309,15 @@ enum Commands {
         /// Run organization step
         #[arg(long, default_value_t = false)]
         organize: bool,
+        /// Print an end-of-run summary report of what was processed
+        #[arg(long, default_value_t = false)]
+        summary: bool,
+        /// How to bucket images into destination directories during organize
+        #[arg(long, value_enum, default_value = "day")]
+        bin: BinStrategy,
+        /// With `--bin event`, start a new event when the gap since the previous photo exceeds this many hours
+        #[arg(long, default_value_t = 4)]
+        event_gap_hours: i64,
     },
     /// Download GPX files from Garmin
     DownloadGpx {
@@ -163,11 +330,64 @@ enum Commands {
         /// End date (YYYY-MM-DD), defaults to today
         #[arg(long)]
         end_date: Option<String>,
+        /// Gzip-compress the merged all_activities.gpx output
+        #[arg(long, default_value_t = false)]
+        compress: bool,
+        /// Skip the chronological sort/near-duplicate dedup pass and just concatenate tracks as-is
+        #[arg(long, default_value_t = false)]
+        raw: bool,
+        /// Near-duplicate dedup threshold in milliseconds (ignored with --raw)
+        #[arg(long, default_value_t = 1000)]
+        dedup_threshold_ms: i64,
+    },
+    /// Watch directories and automatically process new photos as they land
+    Watch {
+        /// Directories to watch
+        #[arg(required = true)]
+        dirs: Vec<PathBuf>,
+        #[arg(short = 'z', long, required = true)]
+        timezone: String,
+        #[arg(long, default_value_t = false)]
+        dst: bool,
+        /// Run organization step
+        #[arg(long, default_value_t = false)]
+        organize: bool,
+        /// Seconds to wait for a batch of new files to settle before processing
+        #[arg(long, default_value_t = 30)]
+        debounce: u64,
     },
 }
 
 // --- Helpers ---
 
+/// Serializes the progress lines `run` prints so concurrent `exiftool`/`garmin`
+/// invocations from different worker threads don't interleave mid-line.
+static PRINT_LOCK: Mutex<()> = Mutex::new(());
+
+/// Runs `f` over `items` using up to `jobs` OS threads at a time, returning
+/// results in the same order as `items`. `jobs` is clamped to at least 1.
+fn run_bounded<T, R, F>(mut items: Vec<T>, jobs: usize, f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Send + Sync,
+{
+    let jobs = jobs.max(1);
+    let mut results = Vec::with_capacity(items.len());
+
+    while !items.is_empty() {
+        let batch: Vec<T> = items.drain(..items.len().min(jobs)).collect();
+        thread::scope(|scope| {
+            let handles: Vec<_> = batch.into_iter().map(|item| scope.spawn(|| f(item))).collect();
+            for handle in handles {
+                results.push(handle.join().expect("worker thread panicked"));
+            }
+        });
+    }
+
+    results
+}
+
 fn run(program: &str, args: &[&str], files: &[&str], dry_run: bool) -> Result<()> {
     let mut msg = if dry_run {
         format!("DRY-RUN: {} {}", program, args.join(" "))
@@ -182,7 +402,10 @@ fn run(program: &str, args: &[&str], files: &[&str], dry_run: bool) -> Result<()
             msg.push_str(&format!(" ... (and {} more files)", files.len() - 1));
         }
     }
-    println!("{}", msg.trim());
+    {
+        let _guard = PRINT_LOCK.lock().unwrap();
+        println!("{}", msg.trim());
+    }
 
     if dry_run {
         return Ok(());
@@ -216,6 +439,52 @@ fn run_capture(program: &str, args: &[&str]) -> Result<String> {
     Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
 
+/// Splits `files` into up to `jobs` chunks and runs `program args <chunk>` for
+/// each chunk on its own thread, so a single giant exiftool invocation over
+/// thousands of files becomes several smaller concurrent ones. Failed chunks
+/// are recorded on `summary` (when present) as `"stage: reason"` rather than
+/// aborting the remaining chunks; the first error is still returned.
+fn run_files_chunked(
+    stage: &str,
+    program: &str,
+    args: &[&str],
+    files: &[PathBuf],
+    jobs: usize,
+    dry_run: bool,
+    mut summary: Option<&mut Summary>,
+) -> Result<()> {
+    if files.is_empty() {
+        return Ok(());
+    }
+
+    let jobs = jobs.max(1);
+    let chunk_size = files.len().div_ceil(jobs).max(1);
+    let chunks: Vec<Vec<String>> = files
+        .chunks(chunk_size)
+        .map(|c| c.iter().map(|p| p.to_string_lossy().to_string()).collect())
+        .collect();
+
+    let results: Vec<Result<()>> = run_bounded(chunks, jobs, |chunk| {
+        let refs: Vec<&str> = chunk.iter().map(|s| s.as_str()).collect();
+        run(program, args, &refs, dry_run)
+    });
+
+    let mut first_err = None;
+    for result in results {
+        if let Err(e) = result {
+            eprintln!("{}: a chunk of files failed: {}", stage, e);
+            if let Some(s) = summary.as_deref_mut() {
+                s.failures.push(format!("{}: {}", stage, e));
+            }
+            first_err.get_or_insert(e);
+        }
+    }
+    if let Some(e) = first_err {
+        return Err(e);
+    }
+    Ok(())
+}
+
 fn resolve_files(files: &[PathBuf]) -> Result<Vec<PathBuf>> {
     let mut resolved = Vec::new();
     for path in files {
@@ -231,6 +500,157 @@ fn resolve_files(files: &[PathBuf]) -> Result<Vec<PathBuf>> {
     Ok(resolved)
 }
 
+/// True if `path`'s extension matches a GPX track or one of `config.suffixes`,
+/// i.e. the same test `get_files_recursively` uses to classify a file. Used
+/// by `cmd_watch` to ignore filesystem events for unrelated files (logs,
+/// sidecar lockfiles, etc.) so they don't reset the debounce timer.
+fn is_watched_extension(path: &Path, config: &AppConfig) -> bool {
+    if is_track_path(path) {
+        return true;
+    }
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    config.suffixes.contains(&ext)
+}
+
+/// True for a `.zip`, `.tar`, or `.tar.gz`/`.tgz` archive, the container
+/// formats `extract_archive` knows how to unpack.
+fn is_archive_path(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    let name = name.to_lowercase();
+    name.ends_with(".zip") || name.ends_with(".tar") || name.ends_with(".tar.gz") || name.ends_with(".tgz")
+}
+
+/// True if `path`'s extension is one of `config.suffixes`, the same image
+/// test `get_files_recursively` uses.
+fn has_image_suffix(config: &AppConfig, path: &Path) -> bool {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    config.suffixes.contains(&ext)
+}
+
+/// Unpacks every image (by `config.suffixes`) and GPX/track entry
+/// (`is_track_path`) out of a `.zip`/`.tar`/`.tar.gz` archive into a fresh
+/// temp directory, so callers can hand that directory to
+/// `get_files_recursively` exactly like any other input path. The caller
+/// owns the returned directory and is responsible for removing it once it's
+/// done (see `cmd_geotag`).
+fn extract_archive(config: &AppConfig, archive: &Path) -> Result<PathBuf> {
+    let stem = archive
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("archive");
+    let dest_dir =
+        std::env::temp_dir().join(format!("photo_process_{}_{}", std::process::id(), stem));
+    fs::create_dir_all(&dest_dir)
+        .with_context(|| format!("Failed to create temp dir {:?}", dest_dir))?;
+
+    let name = archive
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    if name.ends_with(".zip") {
+        let file =
+            fs::File::open(archive).with_context(|| format!("Failed to open {:?}", archive))?;
+        let mut zip = zip::ZipArchive::new(file)
+            .with_context(|| format!("Failed to read zip archive {:?}", archive))?;
+        for i in 0..zip.len() {
+            let mut entry = zip.by_index(i)?;
+            if !entry.is_file() {
+                continue;
+            }
+            let Some(entry_path) = entry.enclosed_name() else {
+                continue;
+            };
+            if !is_track_path(&entry_path) && !has_image_suffix(config, &entry_path) {
+                continue;
+            }
+            let Some(file_name) = entry_path.file_name() else {
+                continue;
+            };
+            let mut out = fs::File::create(dest_dir.join(file_name))?;
+            std::io::copy(&mut entry, &mut out)?;
+        }
+    } else {
+        let file =
+            fs::File::open(archive).with_context(|| format!("Failed to open {:?}", archive))?;
+        let reader: Box<dyn std::io::Read> = if name.ends_with(".gz") || name.ends_with(".tgz") {
+            Box::new(flate2::read::GzDecoder::new(file))
+        } else {
+            Box::new(file)
+        };
+        let mut tar = tar::Archive::new(reader);
+        for entry in tar.entries()? {
+            let mut entry = entry?;
+            let entry_path = entry.path()?.into_owned();
+            if !is_track_path(&entry_path) && !has_image_suffix(config, &entry_path) {
+                continue;
+            }
+            let Some(file_name) = entry_path.file_name().map(|n| n.to_os_string()) else {
+                continue;
+            };
+            let mut out = fs::File::create(dest_dir.join(file_name))?;
+            std::io::copy(&mut entry, &mut out)?;
+        }
+    }
+
+    Ok(dest_dir)
+}
+
+/// Expands any archive entries in `paths` into temp directories so that
+/// directory-scanning entry points (`get_files_recursively`,
+/// `get_all_images_from_paths`) can treat archive contents exactly like any
+/// other directory. Returns the expanded path list together with the temp
+/// directories that were created, which the caller must remove once it's
+/// done with them.
+fn expand_archive_inputs(
+    config: &AppConfig,
+    paths: &[PathBuf],
+) -> Result<(Vec<PathBuf>, Vec<PathBuf>)> {
+    let mut expanded = Vec::new();
+    let mut temp_dirs = Vec::new();
+    for path in paths {
+        if is_archive_path(path) {
+            let temp_dir = extract_archive(config, path)?;
+            expanded.push(temp_dir.clone());
+            temp_dirs.push(temp_dir);
+        } else {
+            expanded.push(path.clone());
+        }
+    }
+    Ok((expanded, temp_dirs))
+}
+
+/// Resolves `gps_files` the way `get_all_images_from_paths` already resolves
+/// image paths: a directory is expanded into the GPX/track files it
+/// contains (via `get_files_recursively`) instead of being rejected by
+/// `resolve_files`, which only accepts concrete files.
+fn resolve_gps_inputs(config: &AppConfig, gps_files: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in gps_files {
+        if !path.exists() {
+            return Err(anyhow::anyhow!("File not found: {:?}", path));
+        }
+        if path.is_dir() {
+            let (_, gpx_files) = get_files_recursively(path, config);
+            files.extend(gpx_files);
+        } else {
+            files.push(path.clone());
+        }
+    }
+    resolve_files(&files)
+}
+
 fn get_files_recursively(dir: &Path, config: &AppConfig) -> (Vec<PathBuf>, Vec<PathBuf>) {
     let mut images = Vec::new();
     let mut gpx_files = Vec::new();
@@ -238,15 +658,17 @@ fn get_files_recursively(dir: &Path, config: &AppConfig) -> (Vec<PathBuf>, Vec<P
     for entry in WalkDir::new(dir).into_iter().filter_map(|e| e.ok()) {
         let path = entry.path();
         if path.is_file() {
+            if is_track_path(path) {
+                gpx_files.push(path.to_path_buf());
+                continue;
+            }
+
             let ext = path
                 .extension()
                 .and_then(|e| e.to_str())
                 .unwrap_or("")
                 .to_lowercase();
-
-            if ext == "gpx" {
-                gpx_files.push(path.to_path_buf());
-            } else if config.suffixes.contains(&ext) {
+            if config.suffixes.contains(&ext) {
                 images.push(path.to_path_buf());
             }
         }
@@ -264,7 +686,6 @@ fn get_tz_info(city: &str) -> Result<(i32, String)> {
 }
 
 /// Returns a list of city names that match the given offset (e.g., "+01:00")
-#[allow(dead_code)]
 fn get_cities_by_offset(offset: &str) -> Vec<&str> {
     TZ_CITIES_DATA
         .iter()
@@ -285,8 +706,142 @@ fn get_reverse_timezone_index() -> HashMap<String, Vec<&'static str>> {
     index
 }
 
+/// True for a `.gpx` file, a gzip-compressed `.gpx.gz` file, or any other
+/// `.gz` file (matched case-insensitively on the file name), the GPX inputs
+/// this tool reads transparently.
+fn is_gpx_path(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    let name = name.to_lowercase();
+    // A bare `.gz` (no `.gpx` in the name) is still accepted: some GPS units
+    // and archives ship gzipped tracks without a `.gpx` stem, and
+    // `open_gpx_reader`'s magic-byte sniff plus `gpx::read`'s own parse
+    // error are what ultimately reject anything that isn't really a track.
+    name.ends_with(".gpx") || name.ends_with(".gpx.gz") || name.ends_with(".gz")
+}
+
+/// True for the merged-track output of `merge_gpx`, compressed or not.
+fn is_all_activities_file(path: &Path) -> bool {
+    matches!(
+        path.file_name().and_then(|n| n.to_str()),
+        Some("all_activities.gpx") | Some("all_activities.gpx.gz")
+    )
+}
+
+/// Maps a FIT/TCX track's extension to its gpsbabel `-i` input format code,
+/// or `None` if `path` isn't a format gpsbabel needs to convert for us. A
+/// trailing `.gz` is stripped first, so a gzip-wrapped `activity.fit.gz` is
+/// still recognized as `fit` instead of falling through on a bare `.gz`.
+fn gpsbabel_format(path: &Path) -> Option<&'static str> {
+    let name = path.file_name().and_then(|n| n.to_str())?.to_lowercase();
+    let name = name.strip_suffix(".gz").unwrap_or(&name);
+    match name.rsplit('.').next()? {
+        "fit" => Some("garmin_fit"),
+        "tcx" => Some("gtrnctr"),
+        _ => None,
+    }
+}
+
+/// True for anything `ensure_gpx`/`get_files_recursively` should treat as a
+/// track file: a format `gpsbabel_format` knows how to convert, or GPX
+/// (optionally gzipped). Which of the two a file is gets decided by
+/// `normalize_track`, which tries `gpsbabel_format` first so a gzip-wrapped
+/// FIT/TCX file is converted rather than matching `is_gpx_path`'s bare-`.gz`
+/// fallback and being treated as already-GPX.
+fn is_track_path(path: &Path) -> bool {
+    gpsbabel_format(path).is_some() || is_gpx_path(path)
+}
+
+/// Converts a FIT/TCX track to GPX via gpsbabel, caching the result next to
+/// the source so repeated runs skip the conversion. GPX (and gzip-wrapped
+/// GPX) inputs pass through unchanged. A gzip-wrapped FIT/TCX source (e.g.
+/// `activity.fit.gz`) is decompressed to a sibling temp file first, since
+/// gpsbabel itself doesn't read gzip.
+fn normalize_track(path: &Path, dry_run: bool) -> Result<PathBuf> {
+    let Some(format) = gpsbabel_format(path) else {
+        if is_gpx_path(path) {
+            return Ok(path.to_path_buf());
+        }
+        return Err(anyhow::anyhow!("Unsupported track format: {:?}", path));
+    };
+
+    let is_gzipped = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.to_lowercase().ends_with(".gz"));
+
+    let mut dest = path.to_path_buf();
+    if is_gzipped {
+        dest.set_extension(""); // drop ".gz", leaving the "fit"/"tcx" extension
+    }
+    dest.set_extension("gpx");
+
+    if dest.exists() {
+        return Ok(dest);
+    }
+
+    let source = if is_gzipped {
+        let mut decompressed = path.to_path_buf();
+        decompressed.set_extension(""); // drop ".gz"
+        if !dry_run {
+            let mut reader = open_gpx_reader(path)?;
+            let mut out = fs::File::create(&decompressed)
+                .with_context(|| format!("Failed to create {:?}", decompressed))?;
+            std::io::copy(&mut reader, &mut out)?;
+        }
+        decompressed
+    } else {
+        path.to_path_buf()
+    };
+
+    let source_str = source.to_str().context("Path not UTF-8")?;
+    let dest_str = dest.to_str().context("Path not UTF-8")?;
+
+    run(
+        "gpsbabel",
+        &["-i", format, "-f", source_str, "-o", "gpx", "-F", dest_str],
+        &[],
+        dry_run,
+    )?;
+
+    if is_gzipped && !dry_run {
+        let _ = fs::remove_file(&source);
+    }
+
+    Ok(dest)
+}
+
+/// Opens `path` for reading, transparently gunzipping it if it's
+/// gzip-compressed. Compression is detected by a `.gz` extension or, as a
+/// fallback for archives that lack one, the gzip magic header (`1f 8b`), so
+/// callers that just want GPX content don't need to special-case either case.
+fn open_gpx_reader(path: &Path) -> Result<Box<dyn std::io::Read>> {
+    let file =
+        fs::File::open(path).with_context(|| format!("Failed to open GPX file {:?}", path))?;
+    let mut reader = std::io::BufReader::new(file);
+
+    let has_gz_ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("gz"));
+    let is_gzipped = has_gz_ext || {
+        use std::io::BufRead;
+        reader
+            .fill_buf()
+            .map(|b| b.starts_with(&[0x1f, 0x8b]))
+            .unwrap_or(false)
+    };
+
+    if is_gzipped {
+        Ok(Box::new(flate2::read::GzDecoder::new(reader)))
+    } else {
+        Ok(Box::new(reader))
+    }
+}
+
 fn gpx_name(gps_file: &Path, _dry_run: bool) -> Result<PathBuf> {
-    if gps_file.extension().and_then(|e| e.to_str()) != Some("gpx") {
+    if !is_gpx_path(gps_file) {
         let mut dest = gps_file.parent().unwrap_or(Path::new(".")).to_path_buf();
         dest.push(format!(
             "{}.gpx",
@@ -298,15 +853,14 @@ fn gpx_name(gps_file: &Path, _dry_run: bool) -> Result<PathBuf> {
         return Ok(dest);
     }
 
-    if gps_file.file_name().and_then(|n| n.to_str()) == Some("all_activities.gpx") {
+    if is_all_activities_file(gps_file) {
         return Ok(gps_file.to_path_buf());
     }
 
     // In dry run, we might not be able to read file if it doesn't exist yet (created by previous step?)
     // But here we read existing files.
 
-    let file = fs::File::open(gps_file)?;
-    let reader = std::io::BufReader::new(file);
+    let reader = open_gpx_reader(gps_file)?;
     let gpx_data = gpx::read(reader)?;
 
     let track_name = if let Some(track) = gpx_data.tracks.first() {
@@ -336,26 +890,46 @@ fn gpx_name(gps_file: &Path, _dry_run: bool) -> Result<PathBuf> {
     let name = format!("{}_{}", track_time, track_name);
     let name = name.replace('/', "-");
 
+    // Output is always the canonical, uncompressed `.gpx` name: `ensure_gpx`
+    // decompresses gzipped input on its way to `dest` rather than just
+    // renaming it, so downstream readers never have to special-case `.gz`.
     let mut dest = gps_file.parent().unwrap_or(Path::new(".")).to_path_buf();
     dest.push(format!("{}.gpx", name));
     Ok(dest)
 }
 
 fn ensure_gpx(gps_file: &Path, dry_run: bool) -> Result<PathBuf> {
-    let dest = gpx_name(gps_file, dry_run)?;
+    let gps_file = normalize_track(gps_file, dry_run)?;
 
-    let suffix = gps_file.extension().and_then(|s| s.to_str()).unwrap_or("");
+    if dry_run && !gps_file.exists() {
+        // The FIT/TCX -> GPX conversion above was only a dry-run message, so
+        // there's nothing on disk yet to read a track name/time from.
+        return Ok(gps_file);
+    }
+
+    let dest = gpx_name(&gps_file, dry_run)?;
+    let is_gzipped = gps_file.to_string_lossy().to_lowercase().ends_with(".gz");
 
-    if suffix == "gpx" {
+    if is_gpx_path(&gps_file) {
         if gps_file != dest {
             println!("{:?} -> {:?}", gps_file, dest);
             if !dry_run {
-                fs::rename(gps_file, &dest)?;
+                if is_gzipped {
+                    let mut reader = open_gpx_reader(&gps_file)?;
+                    let mut out = fs::File::create(&dest)
+                        .with_context(|| format!("Failed to create {:?}", dest))?;
+                    std::io::copy(&mut reader, &mut out)
+                        .with_context(|| format!("Failed to decompress {:?}", gps_file))?;
+                    fs::remove_file(&gps_file)?;
+                } else {
+                    fs::rename(&gps_file, &dest)?;
+                }
             }
         }
     } else {
+        let suffix = gps_file.extension().and_then(|s| s.to_str()).unwrap_or("");
         return Err(anyhow::anyhow!(
-            "Unknown format {:?}. Only .gpx is supported.",
+            "Unknown format {:?}. Only .gpx (optionally gzip-compressed) and gpsbabel-convertible .fit/.tcx tracks are supported.",
             suffix
         ));
     }
@@ -363,8 +937,39 @@ fn ensure_gpx(gps_file: &Path, dry_run: bool) -> Result<PathBuf> {
     Ok(dest)
 }
 
-fn merge_gpx(gpx_files: &[PathBuf], output_dir: &Path, dry_run: bool) -> Result<PathBuf> {
-    let dest = output_dir.join("all_activities.gpx");
+/// `merge_gpx`'s chronological-merge knobs, bundled together since they're
+/// always threaded as a group (see `OrganizeOptions` for the same pattern).
+#[derive(Debug, Clone, Copy)]
+struct GpxMergeOptions {
+    compress: bool,
+    /// Skip the chronological sort/dedup pass and just concatenate every
+    /// input file's tracks, routes, and waypoints as-is.
+    raw: bool,
+    /// Points within this many milliseconds of the previously kept point are
+    /// dropped as near-duplicates. Ignored when `raw` is set.
+    dedup_threshold_ms: i64,
+}
+
+/// Merges `gpx_files` into a single chronologically sorted `all_activities.gpx`
+/// track (or `all_activities.gpx.gz` when `compress` is set). Files are
+/// streamed through a k-way merge (a min-heap keyed on point timestamp,
+/// exactly like merging sorted datetime-keyed log files) so overlapping
+/// recordings from multiple devices come out in order, with near-duplicate
+/// points (e.g. the same instant logged by two devices) collapsed. With
+/// `opts.raw` set, the inputs' tracks, routes, and waypoints are instead
+/// concatenated as-is, unsorted (see `concat_gpx_files`). Files that fail
+/// to parse are skipped, not fatal.
+fn merge_gpx(
+    gpx_files: &[PathBuf],
+    output_dir: &Path,
+    dry_run: bool,
+    opts: GpxMergeOptions,
+) -> Result<PathBuf> {
+    let dest = output_dir.join(if opts.compress {
+        "all_activities.gpx.gz"
+    } else {
+        "all_activities.gpx"
+    });
     if dry_run {
         println!(
             "DRY-RUN: Merge {} GPX files into {:?}",
@@ -378,108 +983,563 @@ fn merge_gpx(gpx_files: &[PathBuf], output_dir: &Path, dry_run: bool) -> Result<
         let _ = fs::remove_file(&dest);
     }
 
-    let mut merged_gpx = gpx::Gpx {
-        version: gpx::GpxVersion::Gpx11,
-        ..Default::default()
+    let merged_gpx = if opts.raw {
+        concat_gpx_files(gpx_files)
+    } else {
+        track_points_to_gpx(&merge_and_dedup_track_points(
+            gpx_files,
+            opts.dedup_threshold_ms,
+        ))
     };
 
+    write_gpx_output(&merged_gpx, &dest, opts.compress)?;
+
+    Ok(dest)
+}
+
+/// Parses every track point out of `gpx_files`, skipping `all_activities`
+/// output and files that fail to parse (not fatal), then hands the
+/// resulting per-file streams to `merge_track_point_streams`.
+fn merge_and_dedup_track_points(gpx_files: &[PathBuf], dedup_threshold_ms: i64) -> Vec<TrackPoint> {
+    let mut streams: Vec<Vec<TrackPoint>> = Vec::new();
     for path in gpx_files {
-        if path.file_name().and_then(|n| n.to_str()) == Some("all_activities.gpx") {
+        if is_all_activities_file(path) {
             continue;
         }
-        let file = fs::File::open(path)?;
-        let reader = std::io::BufReader::new(file);
-        let g = gpx::read(reader).with_context(|| format!("Failed to read GPX file {:?}", path))?;
+        match parse_track_points(path) {
+            Ok(points) if !points.is_empty() => streams.push(points),
+            Ok(_) => {}
+            Err(e) => eprintln!("Skipping unreadable GPX {:?}: {}", path, e),
+        }
+    }
+
+    merge_track_point_streams(streams, dedup_threshold_ms)
+}
 
-        merged_gpx.tracks.extend(g.tracks);
-        merged_gpx.routes.extend(g.routes);
-        merged_gpx.waypoints.extend(g.waypoints);
+/// Chronologically merges several time-sorted point streams via a k-way
+/// merge (a min-heap keyed on point timestamp, exactly like merging sorted
+/// datetime-keyed log files) so overlapping recordings from multiple devices
+/// come out in order, with near-duplicate points (e.g. the same instant
+/// logged by two devices) collapsed.
+fn merge_track_point_streams(
+    streams: Vec<Vec<TrackPoint>>,
+    dedup_threshold_ms: i64,
+) -> Vec<TrackPoint> {
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+
+    let mut streams: Vec<std::vec::IntoIter<TrackPoint>> =
+        streams.into_iter().map(|s| s.into_iter()).collect();
+
+    // k-way merge: a min-heap of (timestamp, stream index) always yields the
+    // globally earliest not-yet-emitted point across every input stream.
+    let mut heap: BinaryHeap<Reverse<(DateTime<Utc>, usize)>> = BinaryHeap::new();
+    let mut fronts: Vec<Option<TrackPoint>> = Vec::with_capacity(streams.len());
+    for (i, stream) in streams.iter_mut().enumerate() {
+        let front = stream.next();
+        if let Some(p) = &front {
+            heap.push(Reverse((p.time, i)));
+        }
+        fronts.push(front);
     }
 
-    let file = fs::File::create(&dest)?;
-    let writer = std::io::BufWriter::new(file);
-    gpx::write(&merged_gpx, writer)
-        .with_context(|| format!("Failed to write merged GPX to {:?}", dest))?;
+    let epsilon = Duration::milliseconds(dedup_threshold_ms);
+    let mut merged_points: Vec<TrackPoint> = Vec::new();
 
-    Ok(dest)
-}
+    while let Some(Reverse((_, i))) = heap.pop() {
+        let point = fronts[i].take().expect("heap entry without a front point");
 
-fn geotag_images_dir(config: &AppConfig, gpx: &Path, dir: &Path) -> Result<()> {
-    run(
-        "gpicsync",
-        &[
-            "-g",
-            gpx.to_str().context("Path not UTF-8")?,
-            "-z",
-            "UTC",
-            "-d",
-            dir.to_str().context("Path not UTF-8")?,
-            "--time-range",
-            &config.timerange.to_string(),
-        ],
-        &[],
-        config.dry_run,
-    )
-}
+        let is_near_duplicate = merged_points
+            .last()
+            .map(|prev| point.time - prev.time < epsilon)
+            .unwrap_or(false);
+        if !is_near_duplicate {
+            merged_points.push(point);
+        }
 
-fn clean(files: &[PathBuf], dry_run: bool) -> Result<()> {
-    if dry_run {
-        return Ok(());
+        let next = streams[i].next();
+        if let Some(p) = &next {
+            heap.push(Reverse((p.time, i)));
+        }
+        fronts[i] = next;
     }
-    for path in files {
-        let mut original = path.clone();
-        if let Some(name) = path.file_name() {
-            let mut name_str = name.to_string_lossy().into_owned();
-            name_str.push_str("_original");
-            original.set_file_name(name_str);
 
-            if original.exists() {
-                let _ = fs::remove_file(original);
+    merged_points
+}
+
+/// Concatenates every input file's tracks, routes, and waypoints verbatim,
+/// with no sort or dedup pass, for users of `--raw` who want each recording
+/// kept as its own separate track.
+fn concat_gpx_files(gpx_files: &[PathBuf]) -> gpx::Gpx {
+    let mut tracks = Vec::new();
+    let mut routes = Vec::new();
+    let mut waypoints = Vec::new();
+
+    for path in gpx_files {
+        if is_all_activities_file(path) {
+            continue;
+        }
+        let data = open_gpx_reader(path).map_err(|e| e.to_string()).and_then(|r| {
+            gpx::read(r).map_err(|e| e.to_string())
+        });
+        match data {
+            Ok(data) => {
+                tracks.extend(data.tracks);
+                routes.extend(data.routes);
+                waypoints.extend(data.waypoints);
             }
+            Err(e) => eprintln!("Skipping unreadable GPX {:?}: {}", path, e),
         }
     }
-    Ok(())
-}
 
-fn remove_empty_dirs_recursive(dir: &Path, dry_run: bool) -> Result<()> {
-    if !dir.is_dir() {
-        return Ok(());
+    gpx::Gpx {
+        version: gpx::GpxVersion::Gpx11,
+        tracks,
+        routes,
+        waypoints,
+        ..Default::default()
     }
-    for entry in fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_dir() {
-            remove_empty_dirs_recursive(&path, dry_run)?;
-            if fs::read_dir(&path)?.next().is_none() {
-                if dry_run {
-                    println!("DRY-RUN: Removing empty directory: {:?}", path);
-                } else {
-                    println!("Removing empty directory: {:?}", path);
-                    fs::remove_dir(&path)?;
-                }
-            }
-        }
+}
+
+fn write_gpx_output(gpx_data: &gpx::Gpx, dest: &Path, compress: bool) -> Result<()> {
+    if compress {
+        use std::io::Write;
+
+        let mut buf = Vec::new();
+        gpx::write(gpx_data, &mut buf)
+            .with_context(|| format!("Failed to write merged GPX to {:?}", dest))?;
+
+        let file = fs::File::create(dest)?;
+        let mut encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+        encoder.write_all(&buf)?;
+        encoder.finish()?;
+        Ok(())
+    } else {
+        write_gpx_file(gpx_data, dest)
     }
-    Ok(())
 }
 
-fn parse_offset(s: &str) -> Result<i32> {
-    let s = s.trim();
-    if s.is_empty() {
-        return Err(anyhow::anyhow!("Empty offset"));
+/// Builds an in-memory single-track `gpx::Gpx` document out of `points`,
+/// the shared tail end of both `merge_gpx` and `cmd_bin_gpx`.
+fn track_points_to_gpx(points: &[TrackPoint]) -> gpx::Gpx {
+    let mut segment = gpx::TrackSegment::new();
+    for p in points {
+        let mut wpt = gpx::Waypoint::new(geo_types::Point::new(p.lon, p.lat));
+        wpt.elevation = p.ele;
+        wpt.time = Some(time::OffsetDateTime::from(std::time::SystemTime::from(p.time)).into());
+        segment.points.push(wpt);
     }
-    let sign = if s.starts_with('-') { -1 } else { 1 };
-    let s = s.trim_start_matches('+').trim_start_matches('-');
-    let parts: Vec<&str> = s.split(':').collect();
-    if parts.len() < 2 {
-        return Err(anyhow::anyhow!("Invalid offset format: {}", s));
+
+    let mut track = gpx::Track::new();
+    track.segments.push(segment);
+
+    gpx::Gpx {
+        version: gpx::GpxVersion::Gpx11,
+        tracks: vec![track],
+        ..Default::default()
     }
-    let h: i32 = parts[0].parse()?;
-    let m: i32 = parts[1].parse()?;
-    Ok(sign * (h * 60 + m))
 }
 
-fn format_offset(mins: i32) -> String {
+fn write_gpx_file(gpx_data: &gpx::Gpx, dest: &Path) -> Result<()> {
+    let file = fs::File::create(dest)?;
+    let writer = std::io::BufWriter::new(file);
+    gpx::write(gpx_data, writer).with_context(|| format!("Failed to write GPX to {:?}", dest))
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TrackPoint {
+    time: DateTime<Utc>,
+    lat: f64,
+    lon: f64,
+    ele: Option<f64>,
+    /// Index of the `<trkseg>` this point came from, unique across the whole
+    /// parse. Interpolation never bridges two different segments, even if
+    /// their timestamps happen to be close enough to pass `timerange`.
+    segment_id: usize,
+}
+
+/// Parses every track point out of a GPX file into a single time-sorted vector.
+fn parse_track_points(gpx_path: &Path) -> Result<Vec<TrackPoint>> {
+    let reader = open_gpx_reader(gpx_path)?;
+    let data =
+        gpx::read(reader).with_context(|| format!("Failed to read GPX file {:?}", gpx_path))?;
+
+    let mut points = Vec::new();
+    let mut segment_id = 0;
+    for track in &data.tracks {
+        for segment in &track.segments {
+            for point in &segment.points {
+                let Some(time) = point.time else {
+                    continue;
+                };
+                let Ok(iso) = time.format() else {
+                    continue;
+                };
+                let Ok(dt) = DateTime::parse_from_rfc3339(&iso) else {
+                    continue;
+                };
+                points.push(TrackPoint {
+                    time: dt.with_timezone(&Utc),
+                    lat: point.point().y(),
+                    lon: point.point().x(),
+                    ele: point.elevation,
+                    segment_id,
+                });
+            }
+            segment_id += 1;
+        }
+    }
+    points.sort_by_key(|p| p.time);
+    Ok(points)
+}
+
+/// Returns the `pattern`-formatted bin key(s) `time` falls into: its own bin,
+/// plus a neighbouring bin if `time` is within `margin_secs` of a bin
+/// boundary. This gives points near a boundary the same slack
+/// `interpolate_position` already gives photos via `config.timerange`, so a
+/// photo just past local midnight still finds the track point it needs even
+/// though that point landed in the previous day's bin.
+fn bin_keys_for_point(time: DateTime<Utc>, pattern: &str, margin_secs: u64) -> Vec<String> {
+    let margin = Duration::seconds(margin_secs as i64);
+    let key = time.format(pattern).to_string();
+    let mut keys = vec![key.clone()];
+
+    let before = (time - margin).format(pattern).to_string();
+    if before != key {
+        keys.push(before);
+    }
+    let after = (time + margin).format(pattern).to_string();
+    if after != key {
+        keys.push(after);
+    }
+    keys
+}
+
+/// Splits every top-level GPX file under each of `dirs` (typically the
+/// `all_activities.gpx` `cmd_download_gpx` just wrote there) by `by`,
+/// and writes one `<bin>.gpx` into each matching `--bin`-named subfolder
+/// `cmd_organize` already created, so `cmd_geotag` only has to read the
+/// points relevant to that folder's photos.
+fn cmd_bin_gpx(config: &AppConfig, dirs: &[PathBuf], by: BinStrategy) -> Result<()> {
+    let Some(pattern) = by.strftime_pattern() else {
+        println!("--by event has no fixed folder name to bin GPX tracks into, skipping.");
+        return Ok(());
+    };
+
+    for dir in dirs {
+        if !dir.exists() {
+            return Err(anyhow::anyhow!("Directory does not exist: {:?}", dir));
+        }
+
+        let mut sources = Vec::new();
+        for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {:?}", dir))? {
+            let path = entry?.path();
+            if path.is_file() && is_gpx_path(&path) {
+                sources.push(path);
+            }
+        }
+        if sources.is_empty() {
+            continue;
+        }
+
+        let mut points = Vec::new();
+        for source in &sources {
+            match parse_track_points(source) {
+                Ok(p) => points.extend(p),
+                Err(e) => eprintln!("Skipping unreadable GPX {:?}: {}", source, e),
+            }
+        }
+        if points.is_empty() {
+            continue;
+        }
+        points.sort_by_key(|p| p.time);
+
+        let mut bins: HashMap<String, Vec<TrackPoint>> = HashMap::new();
+        for point in &points {
+            for key in bin_keys_for_point(point.time, pattern, config.timerange) {
+                bins.entry(key).or_default().push(*point);
+            }
+        }
+
+        for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {:?}", dir))? {
+            let folder = entry?.path();
+            if !folder.is_dir() {
+                continue;
+            }
+            let Some(name) = folder.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let Some(bin_points) = bins.get(name) else {
+                continue;
+            };
+
+            let dest = folder.join(format!("{}.gpx", name));
+            println!(
+                "{:?}: binning {} track point(s) into {:?}",
+                dir,
+                bin_points.len(),
+                dest
+            );
+            if config.dry_run {
+                continue;
+            }
+            write_gpx_file(&track_points_to_gpx(bin_points), &dest)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Result of interpolating a photo's position: lat, lon, elevation, and the
+/// distance in seconds to the nearest GPX point used to derive it (0 for an
+/// exact timestamp match, otherwise how far the bracket/endpoint was).
+type InterpolatedPosition = (f64, f64, Option<f64>, i64);
+
+/// Binary-searches `points` for the interval bracketing `t` and linearly
+/// interpolates latitude/longitude/elevation. Returns `None` if the nearest
+/// bracketing (or endpoint) point is farther than `timerange` seconds away.
+fn interpolate_position(
+    points: &[TrackPoint],
+    t: DateTime<Utc>,
+    timerange: u64,
+) -> Option<InterpolatedPosition> {
+    if points.is_empty() {
+        return None;
+    }
+    let max_gap = Duration::seconds(timerange as i64);
+
+    if t <= points[0].time {
+        let gap = points[0].time - t;
+        return (gap <= max_gap)
+            .then(|| (points[0].lat, points[0].lon, points[0].ele, gap.num_seconds()));
+    }
+    let last = &points[points.len() - 1];
+    if t >= last.time {
+        let gap = t - last.time;
+        return (gap <= max_gap).then(|| (last.lat, last.lon, last.ele, gap.num_seconds()));
+    }
+
+    let idx = points.partition_point(|p| p.time <= t);
+    let p0 = &points[idx - 1];
+    let p1 = &points[idx];
+
+    if p0.time == t {
+        return Some((p0.lat, p0.lon, p0.ele, 0));
+    }
+
+    if p1.time - p0.time > max_gap || p0.segment_id != p1.segment_id {
+        // Either the bracket spans a gap larger than we trust, or the two
+        // points come from different track segments (e.g. the recorder was
+        // paused); either way, don't interpolate across it. Fall back to
+        // whichever endpoint is nearer, if it's close enough.
+        let (nearest, gap) = if t - p0.time <= p1.time - t {
+            (p0, t - p0.time)
+        } else {
+            (p1, p1.time - t)
+        };
+        return (gap <= max_gap).then(|| (nearest.lat, nearest.lon, nearest.ele, gap.num_seconds()));
+    }
+
+    let total = (p1.time - p0.time).num_milliseconds() as f64;
+    let elapsed = (t - p0.time).num_milliseconds() as f64;
+    let f = if total > 0.0 { elapsed / total } else { 0.0 };
+
+    let lat = p0.lat + (p1.lat - p0.lat) * f;
+    let lon = p0.lon + (p1.lon - p0.lon) * f;
+    let ele = match (p0.ele, p1.ele) {
+        (Some(e0), Some(e1)) => Some(e0 + (e1 - e0) * f),
+        (Some(e0), None) | (None, Some(e0)) => Some(e0),
+        (None, None) => None,
+    };
+    let gap = (t - p0.time).min(p1.time - t).num_seconds();
+
+    Some((lat, lon, ele, gap))
+}
+
+fn get_datetime_original(file: &Path) -> Result<DateTime<Utc>> {
+    let file_str = file.to_str().context("Path not UTF-8")?;
+    let output = run_capture("exiftool", &["-s3", "-DateTimeOriginal", file_str])?;
+    let naive = NaiveDateTime::parse_from_str(output.trim(), "%Y:%m:%d %H:%M:%S")
+        .with_context(|| format!("Failed to parse DateTimeOriginal for {:?}: {:?}", file, output))?;
+    Ok(DateTime::from_naive_utc_and_offset(naive, Utc))
+}
+
+/// Per-directory geotag counts returned by `geotag_images_dir`.
+#[derive(Debug, Default)]
+struct GeotagStats {
+    geotagged: usize,
+    untagged: usize,
+    min_gap_secs: Option<i64>,
+    max_gap_secs: Option<i64>,
+}
+
+impl GeotagStats {
+    fn record_gap(&mut self, gap: i64) {
+        self.min_gap_secs = Some(self.min_gap_secs.map_or(gap, |m| m.min(gap)));
+        self.max_gap_secs = Some(self.max_gap_secs.map_or(gap, |m| m.max(gap)));
+    }
+}
+
+/// Geotags every image in `dir` by interpolating its position from the
+/// time-sorted GPX track points in `gpx`. Images falling outside
+/// `config.timerange` seconds of track coverage are left untagged.
+fn geotag_images_dir(config: &AppConfig, gpx: &Path, dir: &Path) -> Result<GeotagStats> {
+    let mut stats = GeotagStats::default();
+
+    let points = parse_track_points(gpx)?;
+    if points.is_empty() {
+        println!("No track points found in {:?}, skipping {:?}", gpx, dir);
+        return Ok(stats);
+    }
+
+    // Each image needs its own `-GPSLatitude=...` value, so unlike rename/
+    // set-time/shift these can't share one batched exiftool invocation —
+    // dispatch one exiftool call per image, bounded to `config.jobs` at a time.
+    let (images, _) = get_files_recursively(dir, config);
+    let outcomes: Vec<Result<Option<i64>>> = run_bounded(images, config.jobs, |image| {
+        let when = match get_datetime_original(&image) {
+            Ok(t) => t,
+            Err(e) => {
+                eprintln!("{:?}: could not read capture time, skipping: {}", image, e);
+                return Ok(None);
+            }
+        };
+
+        let Some((lat, lon, ele, gap)) = interpolate_position(&points, when, config.timerange)
+        else {
+            println!(
+                "{:?}: no GPX coverage within {}s, leaving untagged",
+                image, config.timerange
+            );
+            return Ok(None);
+        };
+
+        let lat_ref = if lat >= 0.0 { "N" } else { "S" };
+        let lon_ref = if lon >= 0.0 { "E" } else { "W" };
+
+        let lat_arg = format!("-GPSLatitude={}", lat.abs());
+        let lat_ref_arg = format!("-GPSLatitudeRef={}", lat_ref);
+        let lon_arg = format!("-GPSLongitude={}", lon.abs());
+        let lon_ref_arg = format!("-GPSLongitudeRef={}", lon_ref);
+        let ele_arg = ele.map(|e| format!("-GPSAltitude={}", e));
+
+        let mut args = vec![
+            lat_arg.as_str(),
+            lat_ref_arg.as_str(),
+            lon_arg.as_str(),
+            lon_ref_arg.as_str(),
+        ];
+        if let Some(ele_arg) = &ele_arg {
+            args.push(ele_arg.as_str());
+        }
+        args.push("-overwrite_original");
+
+        let image_str = image.to_string_lossy().to_string();
+        run("exiftool", &args, &[&image_str], config.dry_run)?;
+
+        Ok(Some(gap))
+    });
+
+    let mut first_err = None;
+    for outcome in outcomes {
+        match outcome {
+            Ok(Some(gap)) => {
+                stats.geotagged += 1;
+                stats.record_gap(gap);
+            }
+            Ok(None) => stats.untagged += 1,
+            Err(e) => {
+                first_err.get_or_insert(e);
+            }
+        }
+    }
+    if let Some(e) = first_err {
+        return Err(e);
+    }
+
+    Ok(stats)
+}
+
+fn clean(files: &[PathBuf], dry_run: bool) -> Result<()> {
+    if dry_run {
+        return Ok(());
+    }
+    for path in files {
+        let mut original = path.clone();
+        if let Some(name) = path.file_name() {
+            let mut name_str = name.to_string_lossy().into_owned();
+            name_str.push_str("_original");
+            original.set_file_name(name_str);
+
+            if original.exists() {
+                let _ = fs::remove_file(original);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Recursively removes empty directories under `dir`, returning how many
+/// were removed (or would be, under `dry_run`) for `Summary` to accumulate.
+fn remove_empty_dirs_recursive(dir: &Path, dry_run: bool) -> Result<usize> {
+    let (removed, _) = remove_empty_dirs_inner(dir, dry_run)?;
+    Ok(removed)
+}
+
+/// Does the actual work for `remove_empty_dirs_recursive`, additionally
+/// reporting whether `dir` itself ended up empty. A directory counts as
+/// empty once every entry in it is a subdirectory that was itself emptied
+/// (or would be, under `dry_run`) — tracking this as a return value instead
+/// of re-reading the directory from disk afterward means dry-run correctly
+/// sees nested empty directories as cleared even though nothing was
+/// actually deleted.
+fn remove_empty_dirs_inner(dir: &Path, dry_run: bool) -> Result<(usize, bool)> {
+    if !dir.is_dir() {
+        return Ok((0, false));
+    }
+    let mut removed = 0;
+    let mut is_empty = true;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_dir() {
+            let (sub_removed, sub_empty) = remove_empty_dirs_inner(&path, dry_run)?;
+            removed += sub_removed;
+            if sub_empty {
+                if dry_run {
+                    println!("DRY-RUN: Removing empty directory: {:?}", path);
+                } else {
+                    println!("Removing empty directory: {:?}", path);
+                    fs::remove_dir(&path)?;
+                }
+                removed += 1;
+            } else {
+                is_empty = false;
+            }
+        } else {
+            is_empty = false;
+        }
+    }
+    Ok((removed, is_empty))
+}
+
+fn parse_offset(s: &str) -> Result<i32> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err(anyhow::anyhow!("Empty offset"));
+    }
+    let sign = if s.starts_with('-') { -1 } else { 1 };
+    let s = s.trim_start_matches('+').trim_start_matches('-');
+    let parts: Vec<&str> = s.split(':').collect();
+    if parts.len() < 2 {
+        return Err(anyhow::anyhow!("Invalid offset format: {}", s));
+    }
+    let h: i32 = parts[0].parse()?;
+    let m: i32 = parts[1].parse()?;
+    Ok(sign * (h * 60 + m))
+}
+
+fn format_offset(mins: i32) -> String {
     let sign = if mins >= 0 { "+" } else { "-" };
     let abs_mins = mins.abs();
     let h = abs_mins / 60;
@@ -487,7 +1547,49 @@ fn format_offset(mins: i32) -> String {
     format!("{}{:02}:{:02}", sign, h, m)
 }
 
+/// Reads `OffsetTimeOriginal` (0x9011) straight out of the EXIF IFD with the
+/// `exif` crate, one in-process parse instead of an `exiftool` subprocess.
+/// Only handles the tag set exiftool's own fast path would find directly
+/// (`DateTimeOriginal` to confirm the file actually has timestamp metadata,
+/// then `OffsetTimeOriginal`, which already folds in DST where applicable).
+///
+/// Does NOT decode Canon's separate `TimeZone`/`DaylightSavings` MakerNote
+/// fields — the generic `exif` crate exposes MakerNote only as an opaque
+/// blob, with no vendor-specific sub-tag decoding — so this bails out to
+/// `get_image_offset`'s `exiftool` path whenever a MakerNote is present,
+/// rather than guess. In practice that means the in-process fast path
+/// never engages for Canon JPEGs, which virtually always carry a
+/// MakerNote: for that camera family this is a partial implementation of
+/// "eliminate the exiftool dependency for read-only operations", still
+/// correct (no wrong offsets), but still shelling out to exiftool.
+fn native_image_offset(file: &Path) -> Option<(String, bool)> {
+    let f = fs::File::open(file).ok()?;
+    let mut reader = std::io::BufReader::new(&f);
+    let exif_data = exif::Reader::new().read_from_container(&mut reader).ok()?;
+
+    exif_data.get_field(Tag::DateTimeOriginal, In::PRIMARY)?;
+
+    if exif_data.get_field(Tag::MakerNote, In::PRIMARY).is_some() {
+        return None;
+    }
+
+    let offset_field = exif_data.get_field(Tag::OffsetTimeOriginal, In::PRIMARY)?;
+    let Value::Ascii(ref values) = offset_field.value else {
+        return None;
+    };
+    let offset = std::str::from_utf8(values.first()?).ok()?.trim();
+    if offset.is_empty() {
+        return None;
+    }
+
+    Some((offset.to_string(), false))
+}
+
 fn get_image_offset(file: &Path) -> Result<(String, bool)> {
+    if let Some(native) = native_image_offset(file) {
+        return Ok(native);
+    }
+
     let args = &[
         "-G1",
         "-a",
@@ -562,38 +1664,737 @@ fn get_image_offset(file: &Path) -> Result<(String, bool)> {
 
 // --- Commands ---
 
-fn cmd_organize(config: &AppConfig, dirs: &[PathBuf]) -> Result<()> {
+/// Accumulates per-stage counters across a `cmd_process` run, printed as an
+/// aligned end-of-run report behind the `--summary` flag.
+#[derive(Debug, Default)]
+struct Summary {
+    images_scanned: usize,
+    images_moved: usize,
+    duplicates_found: usize,
+    geotagged: usize,
+    untagged: usize,
+    min_gap_secs: Option<i64>,
+    max_gap_secs: Option<i64>,
+    offsets_applied: usize,
+    gpx_downloaded: usize,
+    gpx_existing: usize,
+    renamed: usize,
+    shifted_to_utc: usize,
+    extensions_fixed: usize,
+    empty_dirs_removed: usize,
+    /// Per-directory/file timezone offsets detected by `detect_timezones`,
+    /// in the order they were recorded.
+    tz_offsets: Vec<(PathBuf, String)>,
+    /// Chunks of files that failed an exiftool invocation under
+    /// `run_files_chunked`, as `"stage: reason"`.
+    failures: Vec<String>,
+}
+
+impl Summary {
+    fn record_gap(&mut self, gap: i64) {
+        self.min_gap_secs = Some(self.min_gap_secs.map_or(gap, |m| m.min(gap)));
+        self.max_gap_secs = Some(self.max_gap_secs.map_or(gap, |m| m.max(gap)));
+    }
+
+    /// Folds another directory's stats into this one, e.g. after collecting
+    /// results from per-directory worker threads.
+    fn merge(&mut self, other: Summary) {
+        self.images_scanned += other.images_scanned;
+        self.images_moved += other.images_moved;
+        self.duplicates_found += other.duplicates_found;
+        self.geotagged += other.geotagged;
+        self.untagged += other.untagged;
+        self.offsets_applied += other.offsets_applied;
+        self.gpx_downloaded += other.gpx_downloaded;
+        self.gpx_existing += other.gpx_existing;
+        self.renamed += other.renamed;
+        self.shifted_to_utc += other.shifted_to_utc;
+        self.extensions_fixed += other.extensions_fixed;
+        self.empty_dirs_removed += other.empty_dirs_removed;
+        self.tz_offsets.extend(other.tz_offsets);
+        self.failures.extend(other.failures);
+        if let Some(gap) = other.min_gap_secs {
+            self.record_gap(gap);
+        }
+        if let Some(gap) = other.max_gap_secs {
+            self.record_gap(gap);
+        }
+    }
+
+    fn print(&self, dry_run: bool) {
+        let title = if dry_run {
+            "Summary (dry-run, actions that would be taken)"
+        } else {
+            "Summary"
+        };
+        println!("\n{}", title);
+        println!("{:<34}{:>10}", "Images scanned:", self.images_scanned);
+        println!(
+            "{:<34}{:>10}",
+            "Images moved into date folders:", self.images_moved
+        );
+        println!(
+            "{:<34}{:>10}",
+            "Duplicate images found:", self.duplicates_found
+        );
+        println!("{:<34}{:>10}", "Images geotagged:", self.geotagged);
+        println!("{:<34}{:>10}", "Images left untagged:", self.untagged);
+        if let (Some(min), Some(max)) = (self.min_gap_secs, self.max_gap_secs) {
+            println!(
+                "{:<34}{:>7}s / {:<6}s",
+                "GPX coverage gap (min/max):", min, max
+            );
+        }
+        println!(
+            "{:<34}{:>10}",
+            "Timezone offsets applied:", self.offsets_applied
+        );
+        println!(
+            "{:<34}{:>10}",
+            "GPX activities downloaded:", self.gpx_downloaded
+        );
+        println!(
+            "{:<34}{:>10}",
+            "GPX activities already present:", self.gpx_existing
+        );
+        println!("{:<34}{:>10}", "Files renamed:", self.renamed);
+        println!("{:<34}{:>10}", "Files shifted to UTC:", self.shifted_to_utc);
+        println!("{:<34}{:>10}", "Extensions fixed:", self.extensions_fixed);
+        println!(
+            "{:<34}{:>10}",
+            "Empty directories removed:", self.empty_dirs_removed
+        );
+        if !self.tz_offsets.is_empty() {
+            println!("{:<34}{:>10}", "Timezones detected:", self.tz_offsets.len());
+            for (path, offset) in &self.tz_offsets {
+                println!("  - {:?}: {}", path, offset);
+            }
+        }
+        if !self.failures.is_empty() {
+            println!("{:<34}{:>10}", "Failed chunks:", self.failures.len());
+            for failure in &self.failures {
+                println!("  - {}", failure);
+            }
+        }
+    }
+}
+
+const DUPLICATE_INDEX_FILE: &str = ".photo_process_hashes.json";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DuplicateIndex {
+    /// SHA-256 content digest -> path of the first file seen with that digest.
+    digests: HashMap<String, PathBuf>,
+}
+
+fn load_duplicate_index(dir: &Path) -> DuplicateIndex {
+    fs::read_to_string(dir.join(DUPLICATE_INDEX_FILE))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_duplicate_index(dir: &Path, index: &DuplicateIndex) -> Result<()> {
+    let data = serde_json::to_string_pretty(index)?;
+    fs::write(dir.join(DUPLICATE_INDEX_FILE), data)?;
+    Ok(())
+}
+
+const TZ_CACHE_FILE: &str = ".photo_process_tz_cache.json";
+
+/// One cached `detect_timezones` result for a directory: the sample image it
+/// was computed from (so a changed sample invalidates it), the detected
+/// offset/DST, and a hash of the `AppConfig` fields the detection depends on
+/// (so a differently-configured run doesn't reuse a stale result).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TzCacheEntry {
+    sample_image: PathBuf,
+    sample_mtime: u64,
+    offset: String,
+    dst: bool,
+    config_hash: u64,
+}
+
+/// Sidecar cache of per-directory timezone detection, stored the same way
+/// `DuplicateIndex` is: as a JSON file inside the directory it describes.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TzCache {
+    entries: HashMap<PathBuf, TzCacheEntry>,
+}
+
+fn load_tz_cache(dir: &Path) -> TzCache {
+    fs::read_to_string(dir.join(TZ_CACHE_FILE))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_tz_cache(dir: &Path, cache: &TzCache) -> Result<()> {
+    let data = serde_json::to_string_pretty(cache)?;
+    fs::write(dir.join(TZ_CACHE_FILE), data)?;
+    Ok(())
+}
+
+/// Hashes the `AppConfig` fields that affect timezone detection, so a cache
+/// entry written under a different `--suffix`/`--timerange` isn't reused.
+fn hash_app_config(config: &AppConfig) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    config.suffixes.hash(&mut hasher);
+    config.timerange.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn mtime_secs(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    modified
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_secs())
+}
+
+/// Looks up a still-valid cached offset for `path`'s sample image: the cache
+/// must have an entry for `path` whose sample image, mtime, and config hash
+/// all still match.
+fn cached_tz_offset(
+    cache: &TzCache,
+    path: &Path,
+    sample: &Path,
+    config_hash: u64,
+) -> Option<(String, bool)> {
+    let entry = cache.entries.get(path)?;
+    if entry.sample_image != sample || entry.config_hash != config_hash {
+        return None;
+    }
+    if mtime_secs(sample) != Some(entry.sample_mtime) {
+        return None;
+    }
+    Some((entry.offset.clone(), entry.dst))
+}
+
+fn hash_file(path: &Path) -> Result<String> {
+    let bytes = fs::read(path).with_context(|| format!("Failed to read {:?}", path))?;
+    let mut hasher = Sha256::new();
+    hasher.update(&bytes);
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Clusters `images` into "events" by gaps in `DateTimeOriginal`: a new event
+/// starts whenever the time since the previous photo exceeds `gap_hours`.
+/// Each event is named after the calendar date of its first photo, plus an
+/// incrementing index to disambiguate same-day events, or a `first_to_last`
+/// date range when the event spans midnight. Images whose capture time can't
+/// be read are skipped (left in place) with a warning, same as `geotag_images_dir`.
+fn assign_event_dirs(images: &[PathBuf], gap_hours: i64) -> Vec<(PathBuf, String)> {
+    let gap = Duration::hours(gap_hours);
+
+    let mut dated: Vec<(PathBuf, DateTime<Utc>)> = images
+        .iter()
+        .filter_map(|image| match get_datetime_original(image) {
+            Ok(t) => Some((image.clone(), t)),
+            Err(e) => {
+                eprintln!("{:?}: could not read capture time, skipping: {}", image, e);
+                None
+            }
+        })
+        .collect();
+    dated.sort_by_key(|(_, t)| *t);
+
+    let mut events: Vec<Vec<(PathBuf, DateTime<Utc>)>> = Vec::new();
+    for entry in dated {
+        let starts_new_event = match events.last().and_then(|e| e.last()) {
+            Some((_, last)) => entry.1 - *last > gap,
+            None => true,
+        };
+        if starts_new_event {
+            events.push(Vec::new());
+        }
+        events.last_mut().unwrap().push(entry);
+    }
+
+    let mut per_day_index: HashMap<String, usize> = HashMap::new();
+    let mut assignments = Vec::with_capacity(images.len());
+    for event in events {
+        let first_date = event.first().unwrap().1.format("%Y-%m-%d").to_string();
+        let last_date = event.last().unwrap().1.format("%Y-%m-%d").to_string();
+        let dir_name = if first_date == last_date {
+            let index = per_day_index.entry(first_date.clone()).or_insert(0);
+            *index += 1;
+            format!("{}-{:02}", first_date, index)
+        } else {
+            format!("{}_to_{}", first_date, last_date)
+        };
+        for (image, _) in event {
+            assignments.push((image, dir_name.clone()));
+        }
+    }
+
+    assignments
+}
+
+/// Parses a fixed-length bin duration like `"6h"`, `"30m"`, or `"2d"` into
+/// seconds. The last character is the unit (`s`/`m`/`h`/`d`); everything
+/// before it is the count.
+fn parse_duration_secs(s: &str) -> Result<u64> {
+    let s = s.trim();
+    if s.len() < 2 {
+        return Err(anyhow::anyhow!(
+            "Invalid --bin-duration {:?}, expected e.g. \"6h\", \"30m\", \"2d\"",
+            s
+        ));
+    }
+    let (count, unit) = s.split_at(s.len() - 1);
+    let count: u64 = count
+        .parse()
+        .with_context(|| format!("Invalid --bin-duration {:?}", s))?;
+    let multiplier = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Unknown unit in --bin-duration {:?}, expected one of s/m/h/d",
+                s
+            ))
+        }
+    };
+    Ok(count * multiplier)
+}
+
+/// Floors `time` to the start of the `duration_secs`-long window it falls
+/// in (by flooring its Unix timestamp to the nearest multiple of
+/// `duration_secs`) and formats that window's start as a directory name.
+fn duration_bin_label(time: DateTime<Utc>, duration_secs: u64) -> String {
+    let floored = (time.timestamp().max(0) as u64 / duration_secs) * duration_secs;
+    let bin_start = DateTime::from_timestamp(floored as i64, 0).unwrap_or(time);
+    bin_start.format("%Y-%m-%dT%H-%M-%SZ").to_string()
+}
+
+/// Computes the `source -> destination` mapping `cmd_organize` would apply
+/// for one directory, mirroring its three bucketing modes: a
+/// `$DateTimeOriginal` formatted subdirectory for day/week/month/year bins,
+/// a fixed-length `--bin-duration` window, or the event-gap clustering from
+/// `assign_event_dirs` for `--bin event`.
+fn plan_organize(
+    dir: &Path,
+    images: &[PathBuf],
+    bin: BinStrategy,
+    event_gap_hours: i64,
+    bin_duration_secs: Option<u64>,
+) -> Vec<PlanEntry> {
+    let mut entries = Vec::with_capacity(images.len());
+
+    if let Some(duration_secs) = bin_duration_secs {
+        for image in images {
+            let destination = match get_datetime_original(image) {
+                Ok(dt) => dir
+                    .join(duration_bin_label(dt, duration_secs))
+                    .join(image.file_name().unwrap_or_default())
+                    .to_string_lossy()
+                    .to_string(),
+                Err(e) => {
+                    eprintln!("{:?}: could not read DateTimeOriginal: {}", image, e);
+                    image.to_string_lossy().to_string()
+                }
+            };
+            entries.push(PlanEntry {
+                source: image.to_string_lossy().to_string(),
+                destination,
+                collision: false,
+            });
+        }
+        flag_collisions(&mut entries);
+        return entries;
+    }
+
+    match bin.strftime_pattern() {
+        Some(pattern) => {
+            for image in images {
+                let destination = match get_datetime_original(image) {
+                    Ok(dt) => dir
+                        .join(dt.format(pattern).to_string())
+                        .join(image.file_name().unwrap_or_default())
+                        .to_string_lossy()
+                        .to_string(),
+                    Err(e) => {
+                        eprintln!("{:?}: could not read DateTimeOriginal: {}", image, e);
+                        image.to_string_lossy().to_string()
+                    }
+                };
+                entries.push(PlanEntry {
+                    source: image.to_string_lossy().to_string(),
+                    destination,
+                    collision: false,
+                });
+            }
+        }
+        None => {
+            for (image, event_dir) in assign_event_dirs(images, event_gap_hours) {
+                let destination = dir
+                    .join(&event_dir)
+                    .join(image.file_name().unwrap_or_default())
+                    .to_string_lossy()
+                    .to_string();
+                entries.push(PlanEntry {
+                    source: image.to_string_lossy().to_string(),
+                    destination,
+                    collision: false,
+                });
+            }
+        }
+    }
+
+    flag_collisions(&mut entries);
+    entries
+}
+
+fn cmd_organize(
+    config: &AppConfig,
+    dirs: &[PathBuf],
+    delete_duplicates: bool,
+    bin: BinStrategy,
+    event_gap_hours: i64,
+    bin_duration_secs: Option<u64>,
+    mut summary: Option<&mut Summary>,
+) -> Result<()> {
     for dir in dirs {
         if !dir.exists() {
             return Err(anyhow::anyhow!("Directory does not exist: {:?}", dir));
         }
         let (images, _) = get_files_recursively(dir, config);
+        if let Some(s) = summary.as_deref_mut() {
+            s.images_scanned += images.len();
+        }
         if images.is_empty() {
             println!("No images found in {:?}", dir);
             continue;
         }
 
-        let abs_dir = fs::canonicalize(dir)?;
-        let abs_dir_str = abs_dir.to_str().context("Path not UTF-8")?;
+        let mut dup_index = load_duplicate_index(dir);
+        let duplicates_dir = dir.join("duplicates");
+        let mut to_move = Vec::new();
+        // Digest of each `to_move` entry that was freshly indexed below (not
+        // recorded for images whose hash failed), so the index can be
+        // repointed at each image's post-move path once we know it.
+        let mut to_move_digests: HashMap<PathBuf, String> = HashMap::new();
 
-        // We want to move every image under `dir` to `dir/YYYY-MM-DD/`
-        let dir_target = format!("-Directory<{}/$DateTimeOriginal", abs_dir_str);
-        let args = vec!["-d", "%Y-%m-%d", &dir_target];
+        for image in images {
+            let digest = match hash_file(&image) {
+                Ok(d) => d,
+                Err(e) => {
+                    eprintln!("Failed to hash {:?}, organizing anyway: {}", image, e);
+                    to_move.push(image);
+                    continue;
+                }
+            };
 
-        let file_strs: Vec<String> = images
-            .iter()
-            .map(|p| p.to_string_lossy().to_string())
-            .collect();
-        let file_refs: Vec<&str> = file_strs.iter().map(|s| s.as_str()).collect();
+            if let Some(original) = dup_index.digests.get(&digest) {
+                if let Some(s) = summary.as_deref_mut() {
+                    s.duplicates_found += 1;
+                }
+                if delete_duplicates {
+                    println!("{:?} is a duplicate of {:?}, deleting", image, original);
+                    if !config.dry_run {
+                        let _ = fs::remove_file(&image);
+                    }
+                } else {
+                    println!(
+                        "{:?} is a duplicate of {:?}, moving to {:?}",
+                        image, original, duplicates_dir
+                    );
+                    if !config.dry_run {
+                        fs::create_dir_all(&duplicates_dir)?;
+                        let dest = duplicates_dir.join(image.file_name().context("No file name")?);
+                        fs::rename(&image, &dest)?;
+                    }
+                }
+                continue;
+            }
+
+            dup_index.digests.insert(digest.clone(), image.clone());
+            to_move_digests.insert(image.clone(), digest);
+            to_move.push(image);
+        }
 
-        run("exiftool", &args, &file_refs, config.dry_run)?;
+        if to_move.is_empty() {
+            if !config.dry_run {
+                save_duplicate_index(dir, &dup_index)?;
+            }
+            let removed = remove_empty_dirs_recursive(dir, config.dry_run)?;
+            if let Some(s) = summary.as_deref_mut() {
+                s.empty_dirs_removed += removed;
+            }
+            continue;
+        }
+
+        if let Some(s) = summary.as_deref_mut() {
+            s.images_moved += to_move.len();
+        }
 
-        remove_empty_dirs_recursive(dir, config.dry_run)?;
+        // Destination path for every moved image, so the duplicate index
+        // (keyed by digest) can be repointed from the pre-move source path
+        // to where the file actually ended up, instead of going stale the
+        // moment this function renames it.
+        let mut moved: HashMap<PathBuf, PathBuf> = HashMap::new();
+
+        if let Some(duration_secs) = bin_duration_secs {
+            for image in &to_move {
+                let time = match get_datetime_original(image) {
+                    Ok(t) => t,
+                    Err(e) => {
+                        eprintln!("{:?}: could not read capture time, skipping: {}", image, e);
+                        continue;
+                    }
+                };
+                let dest_dir = dir.join(duration_bin_label(time, duration_secs));
+                let dest = dest_dir.join(image.file_name().context("No file name")?);
+                println!("Organizing {:?} -> {:?}", image, dest);
+                if !config.dry_run {
+                    fs::create_dir_all(&dest_dir)?;
+                    fs::rename(image, &dest)?;
+                }
+                moved.insert(image.clone(), dest);
+            }
+        } else {
+            match bin.strftime_pattern() {
+                Some(pattern) => {
+                    let abs_dir = fs::canonicalize(dir)?;
+                    let abs_dir_str = abs_dir.to_str().context("Path not UTF-8")?;
+
+                    // We want to move every image under `dir` to `dir/<bin>/`
+                    let dir_target = format!("-Directory<{}/$DateTimeOriginal", abs_dir_str);
+                    let args = vec!["-d", pattern, &dir_target];
+
+                    let file_strs: Vec<String> = to_move
+                        .iter()
+                        .map(|p| p.to_string_lossy().to_string())
+                        .collect();
+                    let file_refs: Vec<&str> = file_strs.iter().map(|s| s.as_str()).collect();
+
+                    // Capture times are read before exiftool moves the files
+                    // away from the paths in `to_move`.
+                    let times: Vec<Option<DateTime<Utc>>> = if config.dry_run {
+                        Vec::new()
+                    } else {
+                        to_move.iter().map(|p| get_datetime_original(p).ok()).collect()
+                    };
+
+                    run("exiftool", &args, &file_refs, config.dry_run)?;
+
+                    // exiftool does its own rename internally based on the
+                    // same `DateTimeOriginal` tag, so mirror its bin choice
+                    // here by formatting each image's own timestamp with the
+                    // same pattern, to learn where it now lives.
+                    if !config.dry_run {
+                        for (image, time) in to_move.iter().zip(&times) {
+                            let Some(time) = time else {
+                                continue;
+                            };
+                            let dest_dir = dir.join(time.format(pattern).to_string());
+                            let Some(name) = image.file_name() else {
+                                continue;
+                            };
+                            moved.insert(image.clone(), dest_dir.join(name));
+                        }
+                    }
+                }
+                None => {
+                    for (image, event_dir) in assign_event_dirs(&to_move, event_gap_hours) {
+                        let dest_dir = dir.join(&event_dir);
+                        let dest = dest_dir.join(image.file_name().context("No file name")?);
+                        println!("Organizing {:?} -> {:?}", image, dest);
+                        if !config.dry_run {
+                            fs::create_dir_all(&dest_dir)?;
+                            fs::rename(&image, &dest)?;
+                        }
+                        moved.insert(image, dest);
+                    }
+                }
+            }
+        }
+
+        for (old, new) in &moved {
+            if let Some(digest) = to_move_digests.get(old) {
+                dup_index.digests.insert(digest.clone(), new.clone());
+            }
+        }
+
+        if !config.dry_run {
+            save_duplicate_index(dir, &dup_index)?;
+        }
+
+        let removed = remove_empty_dirs_recursive(dir, config.dry_run)?;
+        if let Some(s) = summary.as_deref_mut() {
+            s.empty_dirs_removed += removed;
+        }
     }
     Ok(())
 }
 
-fn fix_extensions(config: &AppConfig, files: &[PathBuf]) -> Result<Vec<PathBuf>> {
+/// `--keep-*` counts for `cmd_prune`, bundled together since they're always
+/// threaded as a group (see `OrganizeOptions` for the same pattern).
+#[derive(Debug, Clone, Copy)]
+struct RetentionCounts {
+    keep_last: usize,
+    keep_daily: usize,
+    keep_weekly: usize,
+    keep_monthly: usize,
+    keep_yearly: usize,
+}
+
+/// One `YYYY-MM-DD` folder under a `cmd_prune` target, plus which retention
+/// rule(s) (if any) kept it.
+struct PruneEntry {
+    path: PathBuf,
+    date: NaiveDate,
+    retained_by: Vec<&'static str>,
+}
+
+/// Finds every immediate subdirectory of `dirs` named `YYYY-MM-DD` (the same
+/// layout `cmd_organize` produces and `cmd_process` scans for its date
+/// range), sorted most-recent-first.
+fn collect_dated_folders(dirs: &[PathBuf]) -> Result<Vec<(NaiveDate, PathBuf)>> {
+    let date_re = regex::Regex::new(r"^\d{4}-\d{2}-\d{2}$")?;
+    let mut folders = Vec::new();
+
+    for dir in dirs {
+        let entries = fs::read_dir(dir).with_context(|| format!("Failed to read {:?}", dir))?;
+        for entry in entries.filter_map(|e| e.ok()) {
+            if !entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if date_re.is_match(&name) {
+                if let Ok(date) = NaiveDate::parse_from_str(&name, "%Y-%m-%d") {
+                    folders.push((date, entry.path()));
+                }
+            }
+        }
+    }
+
+    folders.sort_by_key(|(date, _)| std::cmp::Reverse(*date));
+    Ok(folders)
+}
+
+/// Walks `folders` (already sorted most-recent-first) assigning each entry a
+/// period key; an entry is kept the first time its key is seen, up to
+/// `limit` keeps total. A `limit` of 0 disables the rule entirely.
+fn apply_retention_rule(
+    folders: &[(NaiveDate, PathBuf)],
+    limit: usize,
+    key_fn: fn(usize, NaiveDate) -> String,
+) -> Vec<bool> {
+    let mut kept = vec![false; folders.len()];
+    if limit == 0 {
+        return kept;
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    let mut count = 0;
+    for (i, (date, _)) in folders.iter().enumerate() {
+        if count >= limit {
+            break;
+        }
+        if seen.insert(key_fn(i, *date)) {
+            kept[i] = true;
+            count += 1;
+        }
+    }
+    kept
+}
+
+/// A named `--keep-*` rule: its limit and the period-key function used to
+/// dedup entries within that period.
+type RetentionRule = (&'static str, usize, fn(usize, NaiveDate) -> String);
+
+/// Applies every `--keep-*` rule independently and unions the result: an
+/// entry is retained if ANY rule keeps it, removed otherwise.
+fn plan_prune(folders: &[(NaiveDate, PathBuf)], retention: RetentionCounts) -> Vec<PruneEntry> {
+    let rules: &[RetentionRule] = &[
+        ("last", retention.keep_last, |i, _| i.to_string()),
+        ("daily", retention.keep_daily, |_, d| {
+            d.format("%Y-%m-%d").to_string()
+        }),
+        ("weekly", retention.keep_weekly, |_, d| {
+            let week = d.iso_week();
+            format!("{}-W{:02}", week.year(), week.week())
+        }),
+        ("monthly", retention.keep_monthly, |_, d| {
+            d.format("%Y-%m").to_string()
+        }),
+        ("yearly", retention.keep_yearly, |_, d| {
+            d.format("%Y").to_string()
+        }),
+    ];
+
+    let mut retained_by: Vec<Vec<&'static str>> = vec![Vec::new(); folders.len()];
+    for (name, limit, key_fn) in rules {
+        for (i, kept) in apply_retention_rule(folders, *limit, *key_fn).into_iter().enumerate() {
+            if kept {
+                retained_by[i].push(name);
+            }
+        }
+    }
+
+    folders
+        .iter()
+        .zip(retained_by)
+        .map(|((date, path), retained_by)| PruneEntry {
+            path: path.clone(),
+            date: *date,
+            retained_by,
+        })
+        .collect()
+}
+
+fn cmd_prune(config: &AppConfig, dirs: &[PathBuf], retention: RetentionCounts) -> Result<()> {
+    let folders = collect_dated_folders(dirs)?;
+    if folders.is_empty() {
+        println!("No date folders found.");
+        return Ok(());
+    }
+
+    let entries = plan_prune(&folders, retention);
+    let (mut kept, mut removed) = (0, 0);
+
+    for entry in &entries {
+        if entry.retained_by.is_empty() {
+            removed += 1;
+            println!(
+                "{}: remove {:?}",
+                entry.date.format("%Y-%m-%d"),
+                entry.path
+            );
+            if !config.dry_run {
+                fs::remove_dir_all(&entry.path)
+                    .with_context(|| format!("Failed to remove {:?}", entry.path))?;
+            }
+        } else {
+            kept += 1;
+            println!(
+                "{}: keep {:?} (retained by: {})",
+                entry.date.format("%Y-%m-%d"),
+                entry.path,
+                entry.retained_by.join(", ")
+            );
+        }
+    }
+
+    println!(
+        "{}kept {}, removed {}",
+        if config.dry_run { "DRY-RUN: " } else { "" },
+        kept,
+        removed
+    );
+    Ok(())
+}
+
+fn fix_extensions(
+    config: &AppConfig,
+    files: &[PathBuf],
+    mut summary: Option<&mut Summary>,
+) -> Result<Vec<PathBuf>> {
     let mut resolved = Vec::new();
     let files = resolve_files(files)?;
 
@@ -618,9 +2419,15 @@ fn fix_extensions(config: &AppConfig, files: &[PathBuf]) -> Result<Vec<PathBuf>>
                             resolved.push(path);
                         }
                     } else {
+                        if let Some(s) = summary.as_deref_mut() {
+                            s.extensions_fixed += 1;
+                        }
                         resolved.push(new_path);
                     }
                 } else {
+                    if let Some(s) = summary.as_deref_mut() {
+                        s.extensions_fixed += 1;
+                    }
                     resolved.push(new_path); // assume successful for dry-run flow logic?
                 }
             } else {
@@ -633,9 +2440,63 @@ fn fix_extensions(config: &AppConfig, files: &[PathBuf]) -> Result<Vec<PathBuf>>
     Ok(resolved)
 }
 
-fn cmd_rename(config: &AppConfig, paths: &[PathBuf]) -> Result<()> {
+/// Computes the `source -> destination` mapping `cmd_rename` would apply,
+/// following the same `%Y-%m-%d %H.%M.%S%-c.%e` template exiftool uses
+/// (`%-c` is the copy-number suffix inserted on the 2nd+ file that would
+/// otherwise collide on the same destination).
+fn plan_rename(images: &[PathBuf]) -> Vec<PlanEntry> {
+    let mut seen_dest: HashMap<PathBuf, usize> = HashMap::new();
+    let mut entries = Vec::with_capacity(images.len());
+
+    for image in images {
+        let dir = image.parent().unwrap_or_else(|| Path::new("."));
+        let ext = image
+            .extension()
+            .map(|e| e.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let destination = match get_datetime_original(image) {
+            Ok(dt) => {
+                let base = dt.format("%Y-%m-%d %H.%M.%S").to_string();
+                let count = seen_dest
+                    .entry(dir.join(format!("{}.{}", base, ext)))
+                    .or_insert(0);
+                let name = if *count == 0 {
+                    format!("{}.{}", base, ext)
+                } else {
+                    format!("{}-{}.{}", base, count, ext)
+                };
+                *count += 1;
+                dir.join(name).to_string_lossy().to_string()
+            }
+            Err(e) => {
+                eprintln!("{:?}: could not read DateTimeOriginal: {}", image, e);
+                image.to_string_lossy().to_string()
+            }
+        };
+
+        entries.push(PlanEntry {
+            source: image.to_string_lossy().to_string(),
+            destination,
+            collision: false,
+        });
+    }
+
+    flag_collisions(&mut entries);
+    entries
+}
+
+fn cmd_rename(
+    config: &AppConfig,
+    paths: &[PathBuf],
+    mut summary: Option<&mut Summary>,
+) -> Result<()> {
     let images = get_all_images_from_paths(config, paths);
-    let images = fix_extensions(config, &images)?;
+    let images = fix_extensions(config, &images, summary.as_deref_mut())?;
+
+    if let Some(s) = summary.as_deref_mut() {
+        s.renamed += images.len();
+    }
 
     let img_strs: Vec<String> = images
         .iter()
@@ -652,7 +2513,15 @@ fn cmd_rename(config: &AppConfig, paths: &[PathBuf]) -> Result<()> {
         "-overwrite_original",
     ];
 
-    run("exiftool", &exif_opts, &img_refs, config.dry_run)?;
+    run_files_chunked(
+        "rename",
+        "exiftool",
+        &exif_opts,
+        &images,
+        config.jobs,
+        config.dry_run,
+        summary,
+    )?;
 
     clean(&images, config.dry_run)?;
     Ok(())
@@ -665,6 +2534,7 @@ fn cmd_set_time(
     timezone: &str,
     timezone_id: i32,
     dst: bool,
+    summary: Option<&mut Summary>,
 ) -> Result<()> {
     let images = get_all_images_from_paths(config, paths);
     let images = resolve_files(&images)?;
@@ -692,23 +2562,40 @@ fn cmd_set_time(
         "-overwrite_original",
     ];
 
-    let img_strs: Vec<String> = images
-        .iter()
-        .map(|p| p.to_string_lossy().to_string())
-        .collect();
-    let img_refs: Vec<&str> = img_strs.iter().map(|s| s.as_str()).collect();
+    let mut summary = summary;
+    run_files_chunked(
+        "set-time",
+        "exiftool",
+        &args,
+        &images,
+        config.jobs,
+        config.dry_run,
+        summary.as_deref_mut(),
+    )?;
 
-    run("exiftool", &args, &img_refs, config.dry_run)?;
+    if let Some(s) = summary {
+        s.offsets_applied += images.len();
+    }
     Ok(())
 }
 
-fn cmd_geotag(config: &AppConfig, gps_files: &[PathBuf], paths: &[PathBuf]) -> Result<()> {
+fn cmd_geotag(
+    config: &AppConfig,
+    gps_files: &[PathBuf],
+    paths: &[PathBuf],
+    mut summary: Option<&mut Summary>,
+) -> Result<()> {
     if gps_files.is_empty() {
         return Err(anyhow::anyhow!("No gps files provided"));
     }
-    let images = get_all_images_from_paths(config, paths);
+
+    let (gps_inputs, gps_temp_dirs) = expand_archive_inputs(config, gps_files)?;
+    let (path_inputs, path_temp_dirs) = expand_archive_inputs(config, paths)?;
+    let temp_dirs: Vec<PathBuf> = gps_temp_dirs.into_iter().chain(path_temp_dirs).collect();
+
+    let images = get_all_images_from_paths(config, &path_inputs);
     let images = resolve_files(&images)?;
-    let gps_files = resolve_files(gps_files)?;
+    let gps_files = resolve_gps_inputs(config, &gps_inputs)?;
 
     let mut gps_paths = Vec::new();
     for path in gps_files {
@@ -724,29 +2611,86 @@ fn cmd_geotag(config: &AppConfig, gps_files: &[PathBuf], paths: &[PathBuf]) -> R
         }
     }
 
-    for (dir, _) in dirs {
-        println!("Processing directory: {:?}", dir);
+    let dir_list: Vec<PathBuf> = dirs.into_keys().collect();
+    let mut results: Vec<(PathBuf, Result<GeotagStats>)> =
+        run_bounded(dir_list, config.jobs, |dir| {
+            println!("Processing directory: {:?}", dir);
+
+            let result = (|| -> Result<GeotagStats> {
+                let gpx = if gps_paths.len() > 1 {
+                    merge_gpx(
+                        &gps_paths,
+                        &dir,
+                        config.dry_run,
+                        GpxMergeOptions {
+                            compress: false,
+                            raw: false,
+                            dedup_threshold_ms: 1000,
+                        },
+                    )?
+                } else {
+                    gps_paths[0].clone()
+                };
 
-        let gpx = if gps_paths.len() > 1 {
-            merge_gpx(&gps_paths, &dir, config.dry_run)?
-        } else {
-            gps_paths[0].clone()
-        };
+                let stats = geotag_images_dir(config, &gpx, &dir)?;
 
-        geotag_images_dir(config, &gpx, &dir)?;
+                if gps_paths.len() > 1 && gpx.exists() && !config.dry_run {
+                    if let Err(e) = fs::remove_file(&gpx) {
+                        eprintln!("Failed to remove temporary gpx {:?}: {}", gpx, e);
+                    }
+                }
 
-        if gps_paths.len() > 1 && gpx.exists() && !config.dry_run {
-            if let Err(e) = fs::remove_file(&gpx) {
-                eprintln!("Failed to remove temporary gpx {:?}: {}", gpx, e);
+                Ok(stats)
+            })();
+
+            (dir, result)
+        });
+
+    // Sorted by directory so error reports and summary accumulation follow a
+    // fixed order (`dir_list` comes out of a `HashMap`). Progress and DRY-RUN
+    // lines are printed by the workers themselves, in thread timing order.
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut first_err = None;
+    for (dir, result) in results {
+        match result {
+            Ok(stats) => {
+                if let Some(s) = summary.as_deref_mut() {
+                    s.geotagged += stats.geotagged;
+                    s.untagged += stats.untagged;
+                    if let Some(gap) = stats.min_gap_secs {
+                        s.record_gap(gap);
+                    }
+                    if let Some(gap) = stats.max_gap_secs {
+                        s.record_gap(gap);
+                    }
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to geotag {:?}: {}", dir, e);
+                first_err.get_or_insert(e);
             }
         }
     }
+    for temp_dir in &temp_dirs {
+        let _ = fs::remove_dir_all(temp_dir);
+    }
+
+    if let Some(e) = first_err {
+        return Err(e);
+    }
 
     clean(&images, config.dry_run)?;
     Ok(())
 }
 
-fn cmd_shift(config: &AppConfig, reset_tz: bool, by: &str, paths: &[PathBuf]) -> Result<()> {
+fn cmd_shift(
+    config: &AppConfig,
+    reset_tz: bool,
+    by: &str,
+    paths: &[PathBuf],
+    mut summary: Option<&mut Summary>,
+) -> Result<()> {
     let images = get_all_images_from_paths(config, paths);
     let images = resolve_files(&images)?;
     if by.is_empty() {
@@ -771,13 +2715,19 @@ fn cmd_shift(config: &AppConfig, reset_tz: bool, by: &str, paths: &[PathBuf]) ->
         args.push("-TimezoneCity=");
     }
 
-    let img_strs: Vec<String> = images
-        .iter()
-        .map(|p| p.to_string_lossy().to_string())
-        .collect();
-    let img_refs: Vec<&str> = img_strs.iter().map(|s| s.as_str()).collect();
+    run_files_chunked(
+        "shift",
+        "exiftool",
+        &args,
+        &images,
+        config.jobs,
+        config.dry_run,
+        summary.as_deref_mut(),
+    )?;
 
-    run("exiftool", &args, &img_refs, config.dry_run)?;
+    if let Some(s) = summary {
+        s.shifted_to_utc += images.len();
+    }
     Ok(())
 }
 
@@ -816,26 +2766,63 @@ struct TzDetectionResult {
     offset: Result<(String, bool)>,
 }
 
+/// Detects the UTC offset for every directory under `paths`, one
+/// `get_image_offset` probe per directory, run across up to `config.jobs`
+/// worker threads via `run_bounded` so a trip with many day-folders doesn't
+/// pay for each directory's exiftool call serially. Each directory's result
+/// is cached in a `TzCache` sidecar keyed by its sample image's mtime and
+/// the current config, so re-running `cmd_process`'s detect -> shift ->
+/// re-scan flow doesn't re-probe exiftool for a directory that hasn't
+/// changed; set `config.refresh_tz_cache` to bypass the cache and recompute
+/// everything.
 fn detect_timezones(config: &AppConfig, paths: &[PathBuf]) -> HashMap<PathBuf, TzDetectionResult> {
-    let mut results = HashMap::new();
-    let dir_images = scan_images_from_paths(config, paths);
+    let dir_images: Vec<(PathBuf, Vec<PathBuf>)> =
+        scan_images_from_paths(config, paths).into_iter().collect();
+    let config_hash = hash_app_config(config);
 
-    for (path, images) in dir_images {
-        let offset_res = if let Some(img) = images.first() {
-            get_image_offset(img)
-        } else {
-            Err(anyhow::anyhow!("No images"))
-        };
+    run_bounded(dir_images, config.jobs, |(path, images)| {
+        let offset_res = (|| {
+            let sample = images.first().context("No images")?;
+
+            if path.is_dir() {
+                let mut cache = load_tz_cache(&path);
+
+                if !config.refresh_tz_cache {
+                    if let Some(cached) = cached_tz_offset(&cache, &path, sample, config_hash) {
+                        return Ok(cached);
+                    }
+                }
+
+                let computed = get_image_offset(sample);
+                if let (Ok((offset, dst)), Some(mtime)) = (&computed, mtime_secs(sample)) {
+                    cache.entries.insert(
+                        path.clone(),
+                        TzCacheEntry {
+                            sample_image: sample.clone(),
+                            sample_mtime: mtime,
+                            offset: offset.clone(),
+                            dst: *dst,
+                            config_hash,
+                        },
+                    );
+                    let _ = save_tz_cache(&path, &cache);
+                }
+                computed
+            } else {
+                get_image_offset(sample)
+            }
+        })();
 
-        results.insert(
+        (
             path,
             TzDetectionResult {
                 images,
                 offset: offset_res,
             },
-        );
-    }
-    results
+        )
+    })
+    .into_iter()
+    .collect()
 }
 
 fn cmd_detect_timezone(config: &AppConfig, paths: &[PathBuf]) -> Result<()> {
@@ -846,6 +2833,12 @@ fn cmd_detect_timezone(config: &AppConfig, paths: &[PathBuf]) -> Result<()> {
         return Ok(());
     }
 
+    // Detection runs on a bounded thread pool, so collection order isn't
+    // deterministic; sort by path so `--dry-run`-style inspection output
+    // doesn't reshuffle between runs.
+    let mut results: Vec<(PathBuf, TzDetectionResult)> = results.into_iter().collect();
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+
     for (path, res) in results {
         let label = if path.is_dir() { "Directory" } else { "File" };
         match res.offset {
@@ -864,6 +2857,140 @@ fn cmd_detect_timezone(config: &AppConfig, paths: &[PathBuf]) -> Result<()> {
     Ok(())
 }
 
+/// A single file's worth of `exiftool -j` bulk output, the fields
+/// `build_catalog_rows` pulls into a `CatalogRow`.
+#[derive(Debug, Deserialize)]
+struct ExifJsonEntry {
+    #[serde(rename = "SourceFile")]
+    source_file: String,
+    #[serde(rename = "DateTimeOriginal")]
+    date_time_original: Option<String>,
+    #[serde(rename = "GPSLatitude")]
+    gps_latitude: Option<f64>,
+    #[serde(rename = "GPSLongitude")]
+    gps_longitude: Option<f64>,
+    #[serde(rename = "Model")]
+    camera_model: Option<String>,
+}
+
+/// One row of the photo metadata catalog produced by `Commands::Catalog`.
+#[derive(Debug, Clone, Serialize)]
+struct CatalogRow {
+    filename: String,
+    date_time_original: Option<String>,
+    utc_time: Option<String>,
+    offset: Option<String>,
+    dst: bool,
+    gps_lat: Option<f64>,
+    gps_lon: Option<f64>,
+    camera_model: Option<String>,
+    timezone_city: Option<String>,
+}
+
+/// Builds a catalog row per image. The bulk EXIF fields (DateTimeOriginal,
+/// GPS, camera model) come from a single batched `exiftool -j` call across
+/// all images; the resolved UTC offset reuses the existing per-image
+/// `get_image_offset` probe (in-process EXIF read, falling back to
+/// exiftool), since it needs its own
+/// `-TimeZone`/`-OffsetTimeOriginal`/`-DaylightSavings` tag set and can't be
+/// folded into the bulk pass.
+fn build_catalog_rows(images: &[PathBuf]) -> Result<Vec<CatalogRow>> {
+    let file_strs: Vec<String> = images
+        .iter()
+        .map(|p| p.to_string_lossy().to_string())
+        .collect();
+    let file_refs: Vec<&str> = file_strs.iter().map(|s| s.as_str()).collect();
+
+    let mut args = vec![
+        "-j",
+        "-n",
+        "-DateTimeOriginal",
+        "-GPSLatitude",
+        "-GPSLongitude",
+        "-Model",
+    ];
+    args.extend(file_refs);
+
+    let output = run_capture("exiftool", &args)?;
+    let entries: Vec<ExifJsonEntry> =
+        serde_json::from_str(&output).context("Failed to parse exiftool JSON output")?;
+
+    let mut rows = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let path = PathBuf::from(&entry.source_file);
+        let filename = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| entry.source_file.clone());
+
+        let (offset, dst) = match get_image_offset(&path) {
+            Ok((offset, dst)) => (Some(offset), dst),
+            Err(e) => {
+                eprintln!("{:?}: could not resolve offset: {}", path, e);
+                (None, false)
+            }
+        };
+
+        let utc_time = entry
+            .date_time_original
+            .as_deref()
+            .zip(offset.as_deref())
+            .and_then(|(dto, off)| {
+                let naive = NaiveDateTime::parse_from_str(dto, "%Y:%m:%d %H:%M:%S").ok()?;
+                let mins = parse_offset(off).ok()?;
+                Some(
+                    (naive - Duration::minutes(mins as i64))
+                        .format("%Y-%m-%d %H:%M:%S UTC")
+                        .to_string(),
+                )
+            });
+
+        let timezone_city = offset
+            .as_deref()
+            .and_then(|off| get_cities_by_offset(off).first().map(|c| c.to_string()));
+
+        rows.push(CatalogRow {
+            filename,
+            date_time_original: entry.date_time_original,
+            utc_time,
+            offset,
+            dst,
+            gps_lat: entry.gps_latitude,
+            gps_lon: entry.gps_longitude,
+            camera_model: entry.camera_model,
+            timezone_city,
+        });
+    }
+
+    Ok(rows)
+}
+
+fn cmd_catalog(config: &AppConfig, paths: &[PathBuf], format: CatalogFormat) -> Result<()> {
+    let images = get_all_images_from_paths(config, paths);
+    let images = resolve_files(&images)?;
+    if images.is_empty() {
+        println!("No images found.");
+        return Ok(());
+    }
+
+    let rows = build_catalog_rows(&images)?;
+
+    match format {
+        CatalogFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&rows)?);
+        }
+        CatalogFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(std::io::stdout());
+            for row in &rows {
+                writer.serialize(row)?;
+            }
+            writer.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
 fn cmd_shift_to_utc(config: &AppConfig, paths: &[PathBuf]) -> Result<()> {
     let results = detect_timezones(config, paths);
 
@@ -872,6 +2999,11 @@ fn cmd_shift_to_utc(config: &AppConfig, paths: &[PathBuf]) -> Result<()> {
         return Ok(());
     }
 
+    // Same rationale as `cmd_detect_timezone`: sort for deterministic output
+    // since detection runs on a bounded thread pool.
+    let mut results: Vec<(PathBuf, TzDetectionResult)> = results.into_iter().collect();
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+
     for (path, res) in results {
         let label = if path.is_dir() { "Directory" } else { "File" };
         let (offset_str, dst) = match res.offset {
@@ -908,19 +3040,29 @@ fn cmd_shift_to_utc(config: &AppConfig, paths: &[PathBuf]) -> Result<()> {
         let shift_val = format!("{}{}:{}", shift_sign, parts[0], parts[1]);
 
         println!("  -> Shifting to UTC by {}", shift_val);
-        cmd_shift(config, true, &shift_val, &res.images)?;
+        cmd_shift(config, true, &shift_val, &res.images, None)?;
     }
     Ok(())
 }
 
+/// Binning strategy and parameters for the organize step of `cmd_process`.
+#[derive(Debug, Clone, Copy)]
+struct OrganizeOptions {
+    bin: BinStrategy,
+    event_gap_hours: i64,
+}
+
 fn cmd_process(
     config: &AppConfig,
     dirs: &[PathBuf],
     timezone: &str,
     timezone_id: i32,
     dst: bool,
-    organize: bool,
+    organize: Option<OrganizeOptions>,
+    summary: bool,
 ) -> Result<()> {
+    let mut summary = summary.then(Summary::default);
+
     // 1. Scan and Detect Timezones
     println!("Scanning input directories for images and GPX files...");
     let results = detect_timezones(config, dirs);
@@ -959,6 +3101,10 @@ fn cmd_process(
             if dst_found { "Yes" } else { "No" }
         );
 
+        if let Some(s) = summary.as_mut() {
+            s.tz_offsets.push((path.clone(), offset_str.clone()));
+        }
+
         let (sign, rest) = if offset_str.starts_with('+') || offset_str.starts_with('-') {
             (&offset_str[0..1], &offset_str[1..])
         } else {
@@ -974,37 +3120,99 @@ fn cmd_process(
         let shift_val = format!("{}{}:{}", shift_sign, parts[0], parts[1]);
 
         println!("  -> Shifting to UTC by {}", shift_val);
-        cmd_shift(config, false, &shift_val, &res.images)?;
+        cmd_shift(config, false, &shift_val, &res.images, summary.as_mut())?;
     }
 
     // 3. Organize & Download GPX
-    if organize {
+    if let Some(OrganizeOptions {
+        bin,
+        event_gap_hours,
+    }) = organize
+    {
+        // `dirs` may be individual settled files (see `cmd_watch`), but
+        // organizing, date-range detection, GPX download, and GPX binning
+        // all need real directories to canonicalize/mkdir/read_dir under —
+        // resolve each entry to itself (if it's already a directory) or its
+        // parent (if it's a file), deduplicated so a batch of files settling
+        // in the same directory doesn't organize it more than once.
+        let mut base_dirs: Vec<PathBuf> = Vec::new();
+        for dir in dirs {
+            let base = if dir.is_dir() {
+                dir.clone()
+            } else {
+                dir.parent()
+                    .map(|p| p.to_path_buf())
+                    .unwrap_or_else(|| dir.clone())
+            };
+            if !base_dirs.contains(&base) {
+                base_dirs.push(base);
+            }
+        }
+        let dirs = &base_dirs;
+
         println!("  -> Organizing photos...");
-        cmd_organize(config, dirs)?;
+        cmd_organize(
+            config,
+            dirs,
+            false,
+            bin,
+            event_gap_hours,
+            None,
+            summary.as_mut(),
+        )?;
 
-        // Determine date range from organized folders
-        let mut min_date: Option<NaiveDate> = None;
-        let mut max_date: Option<NaiveDate> = None;
+        // Determine date range from organized folders. Each directory is
+        // scanned on its own thread, with the running min/max reduced behind
+        // a mutex so concurrent scans can't race on the result.
         let date_re = regex::Regex::new(r"^\d{4}-\d{2}-\d{2}$")?;
+        let date_range: Mutex<(Option<NaiveDate>, Option<NaiveDate>)> = Mutex::new((None, None));
+        let date_re = &date_re;
+        let date_range = &date_range;
+
+        for chunk in dirs.chunks(config.jobs.max(1)) {
+            thread::scope(|scope| {
+                for dir in chunk {
+                    scope.spawn(move || {
+                        let mut local_min: Option<NaiveDate> = None;
+                        let mut local_max: Option<NaiveDate> = None;
+
+                        if let Ok(entries) = fs::read_dir(dir) {
+                            for entry in entries.filter_map(|e| e.ok()) {
+                                let name = entry.file_name().to_string_lossy().into_owned();
+                                if date_re.is_match(&name) {
+                                    if let Ok(date) = NaiveDate::parse_from_str(&name, "%Y-%m-%d")
+                                    {
+                                        if local_min.is_none() || date < local_min.unwrap() {
+                                            local_min = Some(date);
+                                        }
+                                        if local_max.is_none() || date > local_max.unwrap() {
+                                            local_max = Some(date);
+                                        }
+                                    }
+                                }
+                            }
+                        }
 
-        for dir in dirs {
-            if let Ok(entries) = fs::read_dir(dir) {
-                for entry in entries.filter_map(|e| e.ok()) {
-                    let name = entry.file_name().to_string_lossy().into_owned();
-                    if date_re.is_match(&name) {
-                        if let Ok(date) = NaiveDate::parse_from_str(&name, "%Y-%m-%d") {
-                            if min_date.is_none() || date < min_date.unwrap() {
-                                min_date = Some(date);
+                        if local_min.is_some() || local_max.is_some() {
+                            let mut range = date_range.lock().unwrap();
+                            if let Some(d) = local_min {
+                                if range.0.is_none() || d < range.0.unwrap() {
+                                    range.0 = Some(d);
+                                }
                             }
-                            if max_date.is_none() || date > max_date.unwrap() {
-                                max_date = Some(date);
+                            if let Some(d) = local_max {
+                                if range.1.is_none() || d > range.1.unwrap() {
+                                    range.1 = Some(d);
+                                }
                             }
                         }
-                    }
+                    });
                 }
-            }
+            });
         }
 
+        let (min_date, max_date) = *date_range.lock().unwrap();
+
         if let (Some(start), Some(end)) = (min_date, max_date) {
             let start_str = start.format("%Y-%m-%d").to_string();
             let end_str = end.format("%Y-%m-%d").to_string();
@@ -1012,48 +3220,133 @@ fn cmd_process(
 
             for dir in dirs {
                 println!("  -> Downloading GPX files to {:?}", dir);
-                cmd_download_gpx(config, dir, Some(&start_str), Some(&end_str))?;
+                cmd_download_gpx(
+                    config,
+                    dir,
+                    Some(&start_str),
+                    Some(&end_str),
+                    GpxMergeOptions {
+                        compress: false,
+                        raw: false,
+                        dedup_threshold_ms: 1000,
+                    },
+                    summary.as_mut(),
+                )?;
             }
+
+            println!("  -> Binning downloaded GPX tracks into date folders...");
+            cmd_bin_gpx(config, dirs, bin)?;
         }
     }
 
-    // 5. Re-scan for processing (images and downloaded GPX)
-    let mut all_images = Vec::new();
-    let mut all_gpx = Vec::new();
-
+    // 5-8. Re-scan, geotag, set time, and rename — one photo-containing
+    // leaf directory per worker thread so a multi-day import doesn't wait
+    // on each directory in turn. Each leaf only gets the GPX file(s) sitting
+    // directly alongside it (the per-day bin `cmd_bin_gpx` just wrote), not
+    // every other day's track, so `cmd_geotag`'s merge has just the one
+    // track relevant to that directory's photos.
+    let mut dir_contents: Vec<(PathBuf, Vec<PathBuf>, Vec<PathBuf>)> = Vec::new();
     for dir in dirs {
-        let (imgs, gpxs) = get_files_recursively(dir, config);
-        all_images.extend(imgs);
-        all_gpx.extend(gpxs);
+        let (images, _) = get_files_recursively(dir, config);
+        let mut images_by_parent: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
+        for image in images {
+            if let Some(parent) = image.parent() {
+                images_by_parent
+                    .entry(parent.to_path_buf())
+                    .or_default()
+                    .push(image);
+            }
+        }
+        for (parent, images) in images_by_parent {
+            let mut gpx = Vec::new();
+            if let Ok(entries) = fs::read_dir(&parent) {
+                for entry in entries.filter_map(|e| e.ok()) {
+                    let path = entry.path();
+                    if path.is_file() && is_gpx_path(&path) {
+                        gpx.push(path);
+                    }
+                }
+            }
+            dir_contents.push((parent, images, gpx));
+        }
     }
 
-    if all_images.is_empty() {
+    if dir_contents.iter().all(|(_, images, _)| images.is_empty()) {
         println!("No images found after organization, finishing.");
         return Ok(());
     }
 
-    // 6. Geotag
-    if !all_gpx.is_empty() {
-        cmd_geotag(config, &all_gpx, &all_images)?;
-    } else {
-        println!("No GPX files found, skipping geotag.");
-    }
+    let mut results: Vec<(PathBuf, Result<Summary>)> =
+        run_bounded(dir_contents, config.jobs, |(dir, images, gpx)| {
+            let result = (|| -> Result<Summary> {
+                let mut local_summary = Summary::default();
 
-    // 7. Set Time (UTC -> Target)
-    println!("Setting time and timezone to {}", timezone);
-    cmd_set_time(config, &all_images, true, timezone, timezone_id, dst)?;
+                if images.is_empty() {
+                    return Ok(local_summary);
+                }
+
+                if !gpx.is_empty() {
+                    cmd_geotag(config, &gpx, &images, Some(&mut local_summary))?;
+                } else {
+                    println!("{:?}: no GPX files found, skipping geotag.", dir);
+                }
+
+                println!("{:?}: setting time and timezone to {}", dir, timezone);
+                cmd_set_time(
+                    config,
+                    &images,
+                    true,
+                    timezone,
+                    timezone_id,
+                    dst,
+                    Some(&mut local_summary),
+                )?;
+
+                cmd_rename(config, &images, Some(&mut local_summary))?;
+
+                Ok(local_summary)
+            })();
+
+            (dir, result)
+        });
+
+    // Sorted by directory so error reports and summary merging follow a
+    // fixed order. Progress and DRY-RUN lines are printed by the workers
+    // themselves, in thread timing order.
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut first_err = None;
+    for (dir, result) in results {
+        match result {
+            Ok(dir_summary) => {
+                if let Some(s) = summary.as_mut() {
+                    s.merge(dir_summary);
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to process {:?}: {}", dir, e);
+                first_err.get_or_insert(e);
+            }
+        }
+    }
+    if let Some(e) = first_err {
+        return Err(e);
+    }
 
-    // 8. Rename
-    cmd_rename(config, &all_images)?;
+    if let Some(s) = &summary {
+        s.print(config.dry_run);
+    }
 
     Ok(())
 }
 
 fn cmd_download_gpx(
-    _config: &AppConfig,
+    config: &AppConfig,
     dest: &Path,
     start_date: Option<&String>,
     end_date: Option<&String>,
+    merge_opts: GpxMergeOptions,
+    mut summary: Option<&mut Summary>,
 ) -> Result<()> {
     let end = match end_date {
         Some(s) => NaiveDate::parse_from_str(s, "%Y-%m-%d")?,
@@ -1072,6 +3365,7 @@ fn cmd_download_gpx(
 
     let mut offset = 0;
     let limit = 100;
+    let mut pending_downloads: Vec<(String, String)> = Vec::new();
 
     loop {
         let offset_str = offset.to_string();
@@ -1125,32 +3419,13 @@ fn cmd_download_gpx(
                 if gpx_path.exists() {
                     println!("Activity {} already downloaded, checking name...", activity_id);
                     let _ = ensure_gpx(&gpx_path, false)?;
+                    if let Some(s) = summary.as_deref_mut() {
+                        s.gpx_existing += 1;
+                    }
                     continue;
                 }
 
-                println!(
-                    "Downloading activity {} ({})...",
-                    activity_id, activity_date_str
-                );
-                let gpx_path_str = gpx_path.to_string_lossy().to_string();
-
-                run(
-                    "garmin",
-                    &[
-                        "activities",
-                        "download",
-                        "-t",
-                        "gpx",
-                        "-o",
-                        &gpx_path_str,
-                        activity_id,
-                    ],
-                    &[],
-                    false,
-                )?;
-
-                // Rename the downloaded GPX file using its track name and time
-                let _ = ensure_gpx(&gpx_path, false)?;
+                pending_downloads.push((activity_id.to_string(), activity_date_str.to_string()));
             }
         }
 
@@ -1160,10 +3435,166 @@ fn cmd_download_gpx(
         offset += limit;
     }
 
+    // Fetch the missing activities concurrently, bounded by `config.jobs`.
+    let mut results: Vec<(String, Result<()>)> = run_bounded(pending_downloads, config.jobs, |(activity_id, activity_date_str)| {
+        println!(
+            "Downloading activity {} ({})...",
+            activity_id, activity_date_str
+        );
+        let gpx_path = dest.join(format!("{}.gpx", activity_id));
+        let gpx_path_str = gpx_path.to_string_lossy().to_string();
+
+        let result = run(
+            "garmin",
+            &[
+                "activities",
+                "download",
+                "-t",
+                "gpx",
+                "-o",
+                &gpx_path_str,
+                &activity_id,
+            ],
+            &[],
+            false,
+        )
+        // Rename the downloaded GPX file using its track name and time
+        .and_then(|()| ensure_gpx(&gpx_path, false).map(|_| ()));
+
+        (activity_id, result)
+    });
+
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut first_err = None;
+    for (activity_id, result) in results {
+        match result {
+            Ok(()) => {
+                if let Some(s) = summary.as_deref_mut() {
+                    s.gpx_downloaded += 1;
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to download activity {}: {}", activity_id, e);
+                first_err.get_or_insert(e);
+            }
+        }
+    }
+    if let Some(e) = first_err {
+        return Err(e);
+    }
+
     // After all downloads and renames, merge everything into all_activities.gpx
-    let (_, gpx_files) = get_files_recursively(dest, _config);
+    let (_, gpx_files) = get_files_recursively(dest, config);
     if !gpx_files.is_empty() {
-        let _ = merge_gpx(&gpx_files, dest, false)?;
+        let _ = merge_gpx(&gpx_files, dest, false, merge_opts)?;
+    }
+
+    Ok(())
+}
+
+fn cmd_watch(
+    config: &AppConfig,
+    dirs: &[PathBuf],
+    timezone: &str,
+    timezone_id: i32,
+    dst: bool,
+    organize: bool,
+    debounce: std::time::Duration,
+) -> Result<()> {
+    use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+    use std::sync::mpsc::{channel, RecvTimeoutError};
+    use std::time::Instant;
+
+    let dirs: Vec<PathBuf> = resolve_files(dirs)?;
+
+    let (tx, rx) = channel();
+    let mut watcher: RecommendedWatcher = notify::recommended_watcher(tx)
+        .with_context(|| "Failed to create filesystem watcher")?;
+
+    for dir in &dirs {
+        watcher
+            .watch(dir, RecursiveMode::Recursive)
+            .with_context(|| format!("Failed to watch {:?}", dir))?;
+    }
+
+    println!(
+        "Watching {} director{} for new photos (debounce: {:?})...",
+        dirs.len(),
+        if dirs.len() == 1 { "y" } else { "ies" },
+        debounce
+    );
+
+    let mut pending: HashMap<PathBuf, Instant> = HashMap::new();
+
+    loop {
+        match rx.recv_timeout(debounce) {
+            Ok(Ok(event)) => {
+                if matches!(
+                    event.kind,
+                    notify::EventKind::Create(_) | notify::EventKind::Modify(_)
+                ) {
+                    for path in event.paths {
+                        if is_watched_extension(&path, config) {
+                            pending.insert(path, Instant::now());
+                        }
+                    }
+                }
+            }
+            Ok(Err(e)) => eprintln!("Watch error: {}", e),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+
+        let now = Instant::now();
+        let settled: Vec<PathBuf> = pending
+            .iter()
+            .filter(|(_, &t)| now.duration_since(t) >= debounce)
+            .map(|(p, _)| p.clone())
+            .collect();
+
+        if settled.is_empty() {
+            continue;
+        }
+
+        for path in &settled {
+            pending.remove(path);
+        }
+
+        // Process exactly the settled paths, not a recursive re-walk of
+        // their parent directories: a watched root can already contain
+        // previously-organized output (from this run's own earlier
+        // batches, or an older `process` run), and re-walking the whole
+        // directory would feed all of that back through the pipeline every
+        // time a single new file lands in it.
+        let settled: Vec<PathBuf> = settled.into_iter().filter(|p| p.exists()).collect();
+        if settled.is_empty() {
+            continue;
+        }
+        let settled = match resolve_files(&settled) {
+            Ok(paths) => paths,
+            Err(e) => {
+                eprintln!("Failed to resolve settled paths: {}", e);
+                continue;
+            }
+        };
+
+        println!("{} file(s) settled, processing...", settled.len());
+        let organize_opts = organize.then_some(OrganizeOptions {
+            bin: BinStrategy::Day,
+            event_gap_hours: 4,
+        });
+        if let Err(e) = cmd_process(
+            config,
+            &settled,
+            timezone,
+            timezone_id,
+            dst,
+            organize_opts,
+            false,
+        ) {
+            eprintln!("Failed to process settled files: {}", e);
+        }
     }
 
     Ok(())
@@ -1181,52 +3612,280 @@ fn main() -> Result<()> {
         suffixes: cli.suffix.iter().map(|s| s.to_lowercase()).collect(),
         timerange: cli.timerange,
         dry_run,
+        jobs: cli.jobs,
+        refresh_tz_cache: cli.refresh_tz_cache,
     };
 
     match &cli.command {
-        Commands::Rename { paths } => cmd_rename(&config, paths)?,
+        Commands::Rename { paths, plan_format } => match plan_format {
+            Some(format) => {
+                let images = get_all_images_from_paths(&config, paths);
+                let images = resolve_files(&images)?;
+                print_plan(&plan_rename(&images), *format)?;
+            }
+            None => cmd_rename(&config, paths, None)?,
+        },
         Commands::SetTime {
             paths,
             timezone,
             dst,
         } => {
             let (tz_id, tz_info) = get_tz_info(timezone)?;
-            cmd_set_time(&config, paths, false, &tz_info, tz_id, *dst)?
+            cmd_set_time(&config, paths, false, &tz_info, tz_id, *dst, None)?
         }
-        Commands::Geotag { gps_files, paths } => cmd_geotag(&config, gps_files, paths)?,
+        Commands::Geotag { gps_files, paths } => cmd_geotag(&config, gps_files, paths, None)?,
         Commands::Shift {
             reset_tz,
             by,
             paths,
-        } => cmd_shift(&config, *reset_tz, by, paths)?,
+        } => cmd_shift(&config, *reset_tz, by, paths, None)?,
         Commands::ShiftToUtc { paths } => cmd_shift_to_utc(&config, paths)?,
         Commands::DetectTimezone { paths } => cmd_detect_timezone(&config, paths)?,
-        Commands::Organize { dirs } => cmd_organize(&config, dirs)?,
+        Commands::Catalog { paths, format } => cmd_catalog(&config, paths, *format)?,
+        Commands::Organize {
+            dirs,
+            delete_duplicates,
+            bin,
+            event_gap_hours,
+            bin_duration,
+            plan_format,
+        } => {
+            let bin_duration_secs = bin_duration
+                .as_deref()
+                .map(parse_duration_secs)
+                .transpose()?;
+            match plan_format {
+                Some(format) => {
+                    for dir in dirs {
+                        let (images, _) = get_files_recursively(dir, &config);
+                        print_plan(
+                            &plan_organize(dir, &images, *bin, *event_gap_hours, bin_duration_secs),
+                            *format,
+                        )?;
+                    }
+                }
+                None => cmd_organize(
+                    &config,
+                    dirs,
+                    *delete_duplicates,
+                    *bin,
+                    *event_gap_hours,
+                    bin_duration_secs,
+                    None,
+                )?,
+            }
+        }
+        Commands::Prune {
+            dirs,
+            keep_last,
+            keep_daily,
+            keep_weekly,
+            keep_monthly,
+            keep_yearly,
+        } => cmd_prune(
+            &config,
+            dirs,
+            RetentionCounts {
+                keep_last: *keep_last,
+                keep_daily: *keep_daily,
+                keep_weekly: *keep_weekly,
+                keep_monthly: *keep_monthly,
+                keep_yearly: *keep_yearly,
+            },
+        )?,
+        Commands::BinGpx { dirs, by } => cmd_bin_gpx(&config, dirs, *by)?,
         Commands::Process {
             dirs,
             timezone,
             dst,
             organize,
+            summary,
+            bin,
+            event_gap_hours,
             ..
         } => {
             let (tz_id, tz_info) = get_tz_info(timezone)?;
+            let organize_opts = organize.then_some(OrganizeOptions {
+                bin: *bin,
+                event_gap_hours: *event_gap_hours,
+            });
             cmd_process(
                 &config,
                 dirs,
                 &tz_info,
                 tz_id,
                 *dst,
-                *organize,
+                organize_opts,
+                *summary,
             )?
         }
         Commands::DownloadGpx {
             dest,
             start_date,
             end_date,
+            compress,
+            raw,
+            dedup_threshold_ms,
+        } => {
+            cmd_download_gpx(
+                &config,
+                dest,
+                start_date.as_ref(),
+                end_date.as_ref(),
+                GpxMergeOptions {
+                    compress: *compress,
+                    raw: *raw,
+                    dedup_threshold_ms: *dedup_threshold_ms,
+                },
+                None,
+            )?;
+        }
+        Commands::Watch {
+            dirs,
+            timezone,
+            dst,
+            organize,
+            debounce,
         } => {
-            cmd_download_gpx(&config, dest, start_date.as_ref(), end_date.as_ref())?;
+            let (tz_id, tz_info) = get_tz_info(timezone)?;
+            cmd_watch(
+                &config,
+                dirs,
+                &tz_info,
+                tz_id,
+                *dst,
+                *organize,
+                std::time::Duration::from_secs(*debounce),
+            )?
         }
     }
 
     Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dt(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(secs, 0).unwrap()
+    }
+
+    fn point(secs: i64, lat: f64, lon: f64, segment_id: usize) -> TrackPoint {
+        TrackPoint {
+            time: dt(secs),
+            lat,
+            lon,
+            ele: None,
+            segment_id,
+        }
+    }
+
+    #[test]
+    fn merge_track_point_streams_orders_across_streams() {
+        let a = vec![point(0, 0.0, 0.0, 0), point(20, 2.0, 2.0, 0)];
+        let b = vec![point(10, 1.0, 1.0, 0), point(30, 3.0, 3.0, 0)];
+
+        let merged = merge_track_point_streams(vec![a, b], 1000);
+
+        let times: Vec<i64> = merged.iter().map(|p| p.time.timestamp()).collect();
+        assert_eq!(times, vec![0, 10, 20, 30]);
+    }
+
+    #[test]
+    fn merge_track_point_streams_drops_near_duplicates() {
+        let a = vec![point(0, 0.0, 0.0, 0), point(10, 1.0, 1.0, 0)];
+        let b = vec![point(0, 0.0, 0.0, 0), point(10, 1.0, 1.0, 0)];
+
+        // Both streams log the same two instants; within the 1s dedup
+        // threshold each pair should collapse to a single kept point.
+        let merged = merge_track_point_streams(vec![a, b], 1000);
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].time.timestamp(), 0);
+        assert_eq!(merged[1].time.timestamp(), 10);
+    }
+
+    #[test]
+    fn interpolate_position_exact_match_has_zero_gap() {
+        let points = vec![point(0, 0.0, 0.0, 0), point(10, 10.0, 10.0, 0)];
+        let (lat, lon, _ele, gap) = interpolate_position(&points, dt(0), 60).unwrap();
+        assert_eq!((lat, lon, gap), (0.0, 0.0, 0));
+    }
+
+    #[test]
+    fn interpolate_position_interpolates_midpoint() {
+        let points = vec![point(0, 0.0, 0.0, 0), point(10, 10.0, 20.0, 0)];
+        let (lat, lon, _ele, gap) = interpolate_position(&points, dt(5), 60).unwrap();
+        assert_eq!((lat, lon, gap), (5.0, 10.0, 5));
+    }
+
+    #[test]
+    fn interpolate_position_refuses_beyond_timerange() {
+        let points = vec![point(0, 0.0, 0.0, 0)];
+        assert!(interpolate_position(&points, dt(1000), 60).is_none());
+    }
+
+    #[test]
+    fn interpolate_position_refuses_across_segment_boundary() {
+        // Same small time gap as a normal bracket, but the points come from
+        // two different track segments (e.g. the recorder was paused), so
+        // this must fall back to the nearest endpoint instead of blending.
+        let points = vec![point(0, 0.0, 0.0, 0), point(10, 10.0, 10.0, 1)];
+        let (lat, lon, _ele, gap) = interpolate_position(&points, dt(4), 60).unwrap();
+        assert_eq!((lat, lon, gap), (0.0, 0.0, 4));
+    }
+
+    #[test]
+    fn bin_keys_for_point_away_from_boundary_has_one_key() {
+        let t = dt(12 * 3600); // noon UTC, well clear of the day boundary
+        let keys = bin_keys_for_point(t, "%Y-%m-%d", 60);
+        assert_eq!(keys.len(), 1);
+    }
+
+    #[test]
+    fn bin_keys_for_point_near_boundary_includes_neighbour() {
+        let t = DateTime::parse_from_rfc3339("2024-01-02T00:00:10+00:00")
+            .unwrap()
+            .with_timezone(&Utc);
+        let keys = bin_keys_for_point(t, "%Y-%m-%d", 60);
+        assert_eq!(keys, vec!["2024-01-02", "2024-01-01"]);
+    }
+
+    #[test]
+    fn apply_retention_rule_keeps_first_n_by_index() {
+        let folders = vec![
+            (NaiveDate::from_ymd_opt(2024, 1, 3).unwrap(), PathBuf::from("3")),
+            (NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(), PathBuf::from("2")),
+            (NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(), PathBuf::from("1")),
+        ];
+        let kept = apply_retention_rule(&folders, 2, |i, _| i.to_string());
+        assert_eq!(kept, vec![true, true, false]);
+    }
+
+    #[test]
+    fn plan_prune_unions_independent_rules() {
+        // keep_last=1 keeps only the most recent folder; keep_monthly=2
+        // independently keeps one folder for each of the 2 most recent
+        // distinct months. A folder kept by either rule must survive the
+        // union even if the other rule drops it.
+        let folders = vec![
+            (NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(), PathBuf::from("feb")),
+            (NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(), PathBuf::from("jan-15")),
+            (NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(), PathBuf::from("jan-1")),
+        ];
+        let retention = RetentionCounts {
+            keep_last: 1,
+            keep_daily: 0,
+            keep_weekly: 0,
+            keep_monthly: 2,
+            keep_yearly: 0,
+        };
+
+        let entries = plan_prune(&folders, retention);
+
+        assert!(!entries[0].retained_by.is_empty()); // kept by both keep_last and keep_monthly
+        assert!(!entries[1].retained_by.is_empty()); // kept by keep_monthly (first Jan entry seen)
+        assert!(entries[2].retained_by.is_empty()); // second Jan entry, no rule keeps it
+    }
 }
\ No newline at end of file